@@ -0,0 +1,196 @@
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::{
+    config::Config,
+    display,
+    error::{AocError, Context, Result, ToErr},
+    file::{self, FileInfo, PathInfo},
+};
+
+pub const ENV: &str = "AOC_ACCOUNT";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const SESSIONS: &str = ".sessions";
+
+#[derive(Default)]
+pub struct Account {
+    name: Option<String>,
+}
+
+impl Account {
+    pub fn new(name: Option<&str>) -> Result<Self> {
+        let name = match name {
+            Some(name) => Some(name.to_string()),
+            None => env::var(ENV).ok().filter(|name| !name.is_empty()),
+        };
+        if let Some(name) = &name {
+            let valid = name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if name.is_empty() || !valid {
+                return AocError::AccountName(name.clone()).err();
+            }
+        }
+        Ok(Self { name })
+    }
+
+    pub fn input(&self) -> String {
+        match &self.name {
+            Some(name) => format!("actual-{name}"),
+            None => "actual".into(),
+        }
+    }
+
+    pub fn session_path(&self, root: &Path) -> PathBuf {
+        match &self.name {
+            Some(name) => root.join(SESSIONS).join(name),
+            None => root.join(".session"),
+        }
+    }
+
+    pub fn session(&self, root: &Path) -> Result<String> {
+        self.session_source(root).map(|(session, _)| session)
+    }
+
+    pub fn session_source(&self, root: &Path) -> Result<(String, Source)> {
+        let (cookie, source) = self
+            .find_session(root)
+            .context("failed to get session cookie")?;
+        let cookie = cookie.trim();
+        let session = if cookie.starts_with("session=") {
+            cookie.to_owned()
+        } else {
+            format!("session={cookie}")
+        };
+        Ok((session, source))
+    }
+
+    fn find_session(&self, root: &Path) -> Result<(String, Source)> {
+        if let Some(name) = &self.name {
+            let path = self.session_path(root);
+            if !path.try_is_file()? {
+                return AocError::NoAccount(name.clone()).err();
+            }
+            return Ok((read_session_file(&path)?, Source::File(path)));
+        }
+        if let Some(cookie) = env::var(SESSION_ENV)
+            .ok()
+            .filter(|cookie| !cookie.is_empty())
+        {
+            return Ok((cookie, Source::Env));
+        }
+        if let Some(command) = Config::load(root)?.session_command {
+            return Ok((run_session_command(&command)?, Source::Command));
+        }
+        if let Some(path) = user_session_path().filter(|path| path.is_file()) {
+            return Ok((read_session_file(&path)?, Source::File(path)));
+        }
+        let path = self.session_path(root);
+        Ok((read_session_file(&path)?, Source::File(path)))
+    }
+
+    pub fn set_session(&self, root: &Path, cookie: &str) -> Result<PathBuf> {
+        let cookie = cookie.trim();
+        let cookie = cookie.strip_prefix("session=").unwrap_or(cookie);
+        if !is_valid_cookie(cookie) {
+            return AocError::SessionFormat.err();
+        }
+        let path = self.session_path(root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(AocError::FileWrite)?;
+        }
+        file::write_private(&path, cookie).context(AocError::FileWrite)?;
+        Ok(path)
+    }
+}
+
+pub enum Source {
+    Env,
+    Command,
+    File(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env => write!(f, "`{SESSION_ENV}`"),
+            Self::Command => write!(f, "`session_command`"),
+            Self::File(path) => write!(f, "{}", display::path(path)),
+        }
+    }
+}
+
+fn read_session_file(path: &Path) -> Result<String> {
+    if is_world_readable(path) {
+        display::info!(
+            "{} is readable by other users, restrict it with `chmod 600`",
+            display::path(path)
+        );
+    }
+    path.read_file().and_then(FileInfo::try_contents)
+}
+
+fn run_session_command(command: &str) -> Result<String> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let output = process::Command::new(shell)
+        .args([flag, command])
+        .stdin(process::Stdio::inherit())
+        .stderr(process::Stdio::inherit())
+        .output()
+        .context(AocError::SessionCommand)?;
+    if !output.status.success() {
+        return AocError::SessionCommand.err();
+    }
+    let cookie = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if cookie.is_empty() {
+        return "no output".err().context(AocError::SessionCommand);
+    }
+    Ok(cookie)
+}
+
+fn user_session_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            if cfg!(windows) {
+                env::var_os("APPDATA").map(PathBuf::from)
+            } else {
+                env::var_os("HOME").map(|home| Path::new(&home).join(".config"))
+            }
+        })?;
+    Some(config_dir.join("aocli").join("session"))
+}
+
+pub fn is_valid_cookie(cookie: &str) -> bool {
+    !cookie.is_empty() && cookie.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(unix)]
+pub fn is_world_readable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o004 != 0)
+}
+
+#[cfg(not(unix))]
+pub fn is_world_readable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(unix)]
+pub fn restrict(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600)).context(AocError::FileWrite)
+}
+
+#[cfg(not(unix))]
+pub fn restrict(_path: &Path) -> Result<()> {
+    Ok(())
+}
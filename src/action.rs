@@ -1,13 +1,24 @@
-use std::{fs, path::Path, process};
+use std::{
+    cmp::Reverse,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process,
+};
 
-use serde::{Deserialize, Serialize};
+use regex::Regex;
+use ring::digest;
 
 use crate::{
-    display,
+    account::{self, Account},
+    crypt, date,
+    display::{self, TimeFormat},
     error::{AocError, Context, ErrorDisplayer, Result, ToErr},
-    file::{FileInfo, PathInfo},
-    network,
-    run::{self, BuildResult},
+    file::{self, PathInfo},
+    inputs, junit,
+    network::{self, Client, DayCompletion, Event, Member, YearCompletion},
+    report::{Case, Outcome, Report},
+    run::{self, BuildOptions, BuildResult, Executable, RunOptions},
     Parts, ROOT,
 };
 
@@ -18,8 +29,20 @@ pub fn init(root: &Path) -> Result<()> {
         root,
         "[workspace]\nmembers = []\nresolver=\"2\"",
     )?;
-    write_project_file(".gitignore", root, "/target\n/.session\n**/[1-2]/out/")?;
-    write_project_file(".session", root, "")?;
+    write_project_file(
+        ".gitignore",
+        root,
+        "/target\n/.session\n/.sessions/\n/.key\n**/[1-2]/out/\n**/data/actual*/input\n\
+        **/data/actual*/[1-2]/answer",
+    )?;
+    let session_path = &root.join(".session");
+    if session_path.try_is_file()? {
+        display::info!("file `.session` already exists");
+        account::restrict(session_path)?;
+    } else {
+        file::write_private(session_path, "").context(AocError::FileWrite)?;
+        display::success!("wrote file `.session`");
+    }
     write_project_file(
         "README.md",
         root,
@@ -54,8 +77,8 @@ fn write_project_file(name: &str, root: &Path, contents: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn new_day(path: &Path, year: &str, day: &str) -> Result<()> {
-    if path.try_exists().context(AocError::FileRead)? {
+pub fn new_day(path: &Path, year: &str, day: &str, options: &BuildOptions) -> Result<()> {
+    if path.try_exists().context(AocError::FileRead)? && !is_kept_data(path)? {
         return AocError::PathExists(display::path(path)).err();
     }
     write_day_files(path, year, day).context(AocError::FileWrite)?;
@@ -63,7 +86,7 @@ pub fn new_day(path: &Path, year: &str, day: &str) -> Result<()> {
     add_workspace_member(path, year, day).context(AocError::WorkspaceMember)?;
     display::success!("added {year}/{day} to workspace");
     display::info!("building crate...");
-    if run::build(path, false, false).display_err().is_some() {
+    if run::build(path, options, false).display_err().is_some() {
         display::success!("finished building crate");
     }
     Ok(())
@@ -101,6 +124,9 @@ fn write_day_files(path: &Path, year: &str, day: &str) -> Result<()> {
             // fn part_2(input: aoc::Input) -> impl ToString {\n//     0\n// }",
         )?;
     }
+    if path.join("data").try_is_dir()? {
+        return Ok(());
+    }
     let data = path.join("data").join("actual");
     fs::create_dir_all(&data)?;
     fs::write(data.join("input"), "")?;
@@ -113,29 +139,306 @@ fn write_day_files(path: &Path, year: &str, day: &str) -> Result<()> {
     Ok(())
 }
 
-#[derive(Serialize, Deserialize)]
-struct CargoToml {
-    workspace: Workspace,
-}
-
-#[derive(Serialize, Deserialize)]
-struct Workspace {
-    members: Vec<String>,
-    resolver: String,
+fn is_kept_data(path: &Path) -> Result<bool> {
+    let mut has_data = false;
+    for entry in path.read_dir().context(AocError::FileRead)? {
+        if entry.context(AocError::FileRead)?.file_name() != "data" {
+            return Ok(false);
+        }
+        has_data = true;
+    }
+    Ok(has_data)
 }
 
 fn add_workspace_member(path: &Path, year: &str, day: &str) -> Result<()> {
     let root = path.parent().unwrap().parent().unwrap();
+    update_workspace_members(root, |members| {
+        members.push(format!("{year}/{day}").into());
+    })
+}
+
+fn remove_workspace_member(root: &Path, year: &str, day: &str) -> Result<()> {
+    let member = format!("{year}/{day}");
+    update_workspace_members(root, |members| {
+        members.retain(|m| m.as_str() != Some(&member));
+    })
+}
+
+fn update_workspace_members(root: &Path, f: impl FnOnce(&mut Vec<toml::Value>)) -> Result<()> {
+    let file = workspace_with_members(root, f)?;
+    fs::write(root.join("Cargo.toml"), file)?;
+    Ok(())
+}
+
+fn workspace_with_members(root: &Path, f: impl FnOnce(&mut Vec<toml::Value>)) -> Result<String> {
     let path = root.join("Cargo.toml");
     let file = fs::read_to_string(&path).context(AocError::WorkspaceCargo)?;
-    let mut toml: CargoToml = toml::from_str(&file).context(AocError::WorkspaceCargo)?;
-    toml.workspace.members.push(format!("{year}/{day}"));
-    let file = toml::to_string(&toml).unwrap();
-    fs::write(&path, file)?;
+    let mut toml: toml::Table = toml::from_str(&file).context(AocError::WorkspaceCargo)?;
+    f(toml
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("members"))
+        .and_then(|members| members.as_array_mut())
+        .ok_or(AocError::WorkspaceCargo)?);
+    Ok(toml::to_string(&toml).unwrap())
+}
+
+pub fn remove_day(root: &Path, year: &str, day: &str, keep_data: bool) -> Result<()> {
+    let path = &root.join(year).join(day);
+    let message = if keep_data {
+        format!("remove the crate for {year}/{day}, keeping its inputs and answers?")
+    } else {
+        format!("remove {year}/{day} including its inputs and answers?")
+    };
+    if !display::confirm(&message) {
+        display::info!("cancelled");
+        return Ok(());
+    }
+    remove_workspace_member(root, year, day).context(AocError::WorkspaceMember)?;
+    display::success!("removed {year}/{day} from workspace");
+    if keep_data {
+        for entry in path.read_dir().context(AocError::FileRead)? {
+            let entry_path = entry.context(AocError::FileRead)?.path();
+            if entry_path.file_name().is_some_and(|name| name == "data") {
+                continue;
+            }
+            if entry_path.try_is_dir()? {
+                fs::remove_dir_all(&entry_path).context(AocError::FileWrite)?;
+            } else {
+                fs::remove_file(&entry_path).context(AocError::FileWrite)?;
+            }
+        }
+        display::success!(
+            "removed crate for {year}/{day}, keeping {}",
+            display::path(&path.join("data"))
+        );
+    } else {
+        fs::remove_dir_all(path).context(AocError::FileWrite)?;
+        remove_empty_year(&root.join(year))?;
+        display::success!("removed {}", display::path(path));
+    }
+    Ok(())
+}
+
+pub fn move_day(root: &Path, year: &str, day: &str, new_year: &str, new_day: &str) -> Result<()> {
+    let path = &root.join(year).join(day);
+    let new_path = &root.join(new_year).join(new_day);
+    if new_path.try_exists().context(AocError::FileRead)? {
+        return AocError::PathExists(display::path(new_path)).err();
+    }
+    let (renames, cargo) =
+        renamed_day_crate(path, year, day, new_year, new_day).context(AocError::DayCargo)?;
+    let member = format!("{year}/{day}");
+    let workspace = workspace_with_members(root, |members| {
+        for m in members.iter_mut() {
+            if m.as_str() == Some(&member) {
+                *m = format!("{new_year}/{new_day}").into();
+            }
+        }
+    })
+    .context(AocError::WorkspaceMember)?;
+    let inputs = inputs::input_names(path)?;
+    if new_day == "25" && day != "25" {
+        for input in &inputs {
+            let answer_path = &path.join("data").join(input).join("2").join("answer");
+            if answer_path.read_file()?.has_contents()
+                || crypt::sealed_path(answer_path).try_is_file()?
+            {
+                return format!("{year}/{day} has part 2 answers, which day 25 does not have")
+                    .err();
+            }
+        }
+    }
+    fs::create_dir_all(root.join(new_year)).context(AocError::FileWrite)?;
+    fs::rename(path, new_path).context(AocError::FileWrite)?;
+    remove_empty_year(&root.join(year))?;
+    let src_path = &new_path.join("src");
+    for (name, new_name) in renames {
+        fs::rename(src_path.join(name), src_path.join(new_name)).context(AocError::FileWrite)?;
+    }
+    fs::write(new_path.join("Cargo.toml"), cargo).context(AocError::DayCargo)?;
+    fs::write(root.join("Cargo.toml"), workspace).context(AocError::WorkspaceMember)?;
+    for input in inputs {
+        let part_path = &new_path.join("data").join(input).join("2");
+        if new_day == "25" && day != "25" && part_path.try_is_dir()? {
+            fs::remove_dir_all(part_path).context(AocError::FileWrite)?;
+        } else if day == "25" && new_day != "25" && !part_path.join("answer").try_is_file()? {
+            fs::create_dir_all(part_path).context(AocError::FileWrite)?;
+            fs::write(part_path.join("answer"), "").context(AocError::FileWrite)?;
+        }
+    }
+    display::success!("moved {year}/{day} to {new_year}/{new_day}");
+    Ok(())
+}
+
+fn renamed_day_crate(
+    path: &Path,
+    year: &str,
+    day: &str,
+    new_year: &str,
+    new_day: &str,
+) -> Result<(Vec<(String, String)>, String)> {
+    let mut renames = Vec::new();
+    for entry in path.join("src").read_dir()? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        if let Some(rest) = name.strip_prefix(day) {
+            if rest == ".rs" || rest.starts_with('-') {
+                renames.push((name.clone(), format!("{new_day}{rest}")));
+            }
+        }
+    }
+    let cargo_path = &path.join("Cargo.toml");
+    let mut toml: toml::Table = toml::from_str(&fs::read_to_string(cargo_path)?)?;
+    let name = run::bin_name(year, day, None);
+    let new_name = run::bin_name(new_year, new_day, None);
+    if let Some(package) = toml
+        .get_mut("package")
+        .and_then(|package| package.as_table_mut())
+    {
+        package.insert("name".into(), new_name.clone().into());
+    }
+    let bins = toml
+        .get_mut("bin")
+        .and_then(|bins| bins.as_array_mut())
+        .into_iter()
+        .flatten()
+        .filter_map(|bin| bin.as_table_mut());
+    for bin in bins {
+        if let Some(rest) = bin
+            .get("name")
+            .and_then(|name| name.as_str())
+            .and_then(|bin_name| bin_name.strip_prefix(&name))
+        {
+            let rest = rest.to_string();
+            bin.insert("name".into(), format!("{new_name}{rest}").into());
+        }
+        if let Some(rest) = bin
+            .get("path")
+            .and_then(|path| path.as_str())
+            .and_then(|bin_path| bin_path.strip_prefix(&format!("src/{day}")))
+        {
+            let rest = rest.to_string();
+            bin.insert("path".into(), format!("src/{new_day}{rest}").into());
+        }
+    }
+    Ok((renames, toml::to_string(&toml).unwrap()))
+}
+
+fn remove_empty_year(path: &Path) -> Result<()> {
+    if path
+        .read_dir()
+        .context(AocError::FileRead)?
+        .next()
+        .is_none()
+    {
+        fs::remove_dir(path).context(AocError::FileWrite)?;
+    }
+    Ok(())
+}
+
+pub fn new_variant(path: &Path, year: &str, day: &str, variant: &str) -> Result<()> {
+    let valid = variant
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if variant.is_empty() || variant == "main" || !valid {
+        return AocError::VariantName(variant.into()).err();
+    }
+    let src_path = &path.join("src");
+    let file_name = format!("{day}-{variant}.rs");
+    let variant_path = &src_path.join(&file_name);
+    if variant_path.try_exists().context(AocError::FileRead)? {
+        return AocError::PathExists(display::path(variant_path)).err();
+    }
+    fs::copy(src_path.join(format!("{day}.rs")), variant_path).context(AocError::FileWrite)?;
+    add_variant_bin(path, year, day, variant, &file_name).context(AocError::VariantBin)?;
+    display::success!(
+        "created variant `{variant}` of {year}/{day} at {}",
+        display::path(variant_path)
+    );
     Ok(())
 }
 
-pub fn add_input(path: &Path, data: &str) -> Result<()> {
+fn add_variant_bin(path: &Path, year: &str, day: &str, variant: &str, file: &str) -> Result<()> {
+    let path = path.join("Cargo.toml");
+    let file_contents = fs::read_to_string(&path)?;
+    let mut toml: toml::Table = toml::from_str(&file_contents)?;
+    let mut bin = toml::Table::new();
+    bin.insert(
+        "name".into(),
+        run::bin_name(year, day, Some(variant)).into(),
+    );
+    bin.insert("path".into(), format!("src/{file}").into());
+    toml.entry("bin")
+        .or_insert_with(|| toml::Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or(AocError::DayCargo)?
+        .push(bin.into());
+    fs::write(&path, toml::to_string(&toml).unwrap())?;
+    Ok(())
+}
+
+pub enum InputSource {
+    Empty,
+    Stdin,
+    File(PathBuf),
+    Clipboard,
+}
+
+impl InputSource {
+    fn read(&self) -> Result<String> {
+        match self {
+            Self::Empty => Ok(String::new()),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("failed to read input from stdin")?;
+                Ok(input)
+            }
+            Self::File(path) => fs::read_to_string(path)
+                .context(AocError::FileRead)
+                .context(AocError::NoFile(display::path(path))),
+            Self::Clipboard => read_clipboard(),
+        }
+    }
+}
+
+fn read_clipboard() -> Result<String> {
+    let commands: &[&[&str]] = if cfg!(target_os = "macos") {
+        &[&["pbpaste"]]
+    } else if cfg!(windows) {
+        &[&["powershell", "-NoProfile", "-Command", "Get-Clipboard -Raw"]]
+    } else {
+        &[
+            &["wl-paste", "--no-newline"],
+            &["xclip", "-selection", "clipboard", "-o"],
+            &["xsel", "--clipboard", "--output"],
+        ]
+    };
+    for command in commands {
+        let Ok(output) = process::Command::new(command[0])
+            .args(&command[1..])
+            .stderr(process::Stdio::null())
+            .output()
+        else {
+            continue;
+        };
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+        }
+    }
+    let tried: Vec<_> = commands.iter().map(|command| command[0]).collect();
+    format!("tried {}", tried.join(", "))
+        .error()
+        .context(AocError::Clipboard)
+        .err()
+}
+
+pub fn add_input(
+    path: &Path,
+    data: &str,
+    source: &InputSource,
+    answers: [Option<&str>; 2],
+) -> Result<()> {
     let mut data_path = path.join("data");
     if !data_path.try_is_dir()? {
         fs::create_dir(&data_path).context(AocError::FileWrite)?;
@@ -149,18 +452,25 @@ pub fn add_input(path: &Path, data: &str) -> Result<()> {
     {
         return AocError::PathExists(display::path(data_path)).err();
     }
-    write_data_files(data_path).context(AocError::FileWrite)?;
+    let input = source.read()?;
+    write_data_files(data_path, &input, answers).context(AocError::FileWrite)?;
     display::success!("created input `{}` at {}", data, display::path(data_path));
     Ok(())
 }
 
-fn write_data_files(path: &Path) -> Result<()> {
+fn write_data_files(path: &Path, input: &str, answers: [Option<&str>; 2]) -> Result<()> {
     fs::create_dir(path)?;
-    fs::write(path.join("input"), "")?;
+    fs::write(path.join("input"), input)?;
     fs::create_dir(path.join("1"))?;
-    fs::write(path.join("1").join("answer"), "")?;
+    fs::write(
+        path.join("1").join("answer"),
+        answers[0].unwrap_or_default(),
+    )?;
     fs::create_dir(path.join("2"))?;
-    fs::write(path.join("2").join("answer"), "")?;
+    fs::write(
+        path.join("2").join("answer"),
+        answers[1].unwrap_or_default(),
+    )?;
     Ok(())
 }
 
@@ -170,61 +480,89 @@ pub fn run_day(
     day: &str,
     input: &str,
     parts: Parts,
-    debug: bool,
+    options: &RunOptions,
 ) -> Result<()> {
+    let input = if input == "actual" {
+        &options.actual
+    } else {
+        input
+    };
     let data_path = &path.join("data").join(input);
-    data_path
-        .join("input")
-        .read_file()?
+    crypt::read(path, &data_path.join("input"))?
         .try_contents()
         .context(AocError::NoInput)?;
-    if !run::build(path, debug, true)?.success() {
+    let BuildResult::Success(executables) = run::build(path, &options.build, true)? else {
         return Ok(());
-    }
+    };
+    let exe = executables.day(year, day, options.variant.as_deref())?;
+    let format = &options.timing.format(year, day);
     match &parts {
         Parts::Default => {
             let mut both_unimplemented = true;
             for part in ["1", "2"] {
-                match run::run(path, year, day, input, part, debug, true)? {
+                match run::run(exe, path, input, part, true, options.timeout)? {
                     run::RunResult::Success { answer, time } => {
-                        let correct = get_correct(data_path, part)?;
+                        let correct = get_correct(path, data_path, part)?;
                         both_unimplemented = false;
-                        display::answer_full(year, day, part, &answer, correct.as_deref(), time);
+                        display::answer_full(
+                            year,
+                            day,
+                            part,
+                            &answer,
+                            correct.as_deref(),
+                            time,
+                            format,
+                        );
                     }
-                    run::RunResult::Panic => {
+                    run::RunResult::Panic { .. } => {
                         both_unimplemented = false;
                         display::day_part(year, day, part);
                         display::panic();
                     }
-                    _ => (),
+                    run::RunResult::Timeout => {
+                        both_unimplemented = false;
+                        display::day_part(year, day, part);
+                        display::timeout();
+                    }
+                    run::RunResult::Unimplemented => (),
                 }
             }
             if both_unimplemented {
                 display::info!("both parts unimplemented");
             }
         }
-        Parts::Part(part) => match run::run(path, year, day, input, part, debug, true)? {
+        Parts::Part(part) => match run::run(exe, path, input, part, true, options.timeout)? {
             run::RunResult::Success { answer, time } => {
-                let correct = get_correct(data_path, part)?;
-                display::answer_full(year, day, part, &answer, correct.as_deref(), time);
+                let correct = get_correct(path, data_path, part)?;
+                display::answer_full(year, day, part, &answer, correct.as_deref(), time, format);
             }
             run::RunResult::Unimplemented => {
                 display::day_part(year, day, part);
                 display::unimplemented();
             }
-            run::RunResult::Panic => {
+            run::RunResult::Panic { .. } => {
                 display::day_part(year, day, part);
                 display::panic();
             }
+            run::RunResult::Timeout => {
+                display::day_part(year, day, part);
+                display::timeout();
+            }
         },
     }
     Ok(())
 }
 
-pub fn run_days(path: &Path, year: &str, days: impl IntoIterator<Item = u8>) -> Result<()> {
+pub fn run_days(
+    path: &Path,
+    year: &str,
+    days: impl IntoIterator<Item = u8>,
+    options: &RunOptions,
+) -> Result<()> {
     let mut total_time = 0;
     let mut num_parts = 0;
     let mut total_days = 0;
+    let mut report = Report::default();
     for day_number in days {
         total_days += 1;
         let day = &format!("{day_number:02}");
@@ -232,88 +570,135 @@ pub fn run_days(path: &Path, year: &str, days: impl IntoIterator<Item = u8>) ->
         if !path.try_is_dir()? {
             continue;
         }
-        if !path
-            .join("data")
-            .join("actual")
-            .join("input")
-            .read_file()?
-            .has_contents()
-        {
+        let input_path = &path.join("data").join(&options.actual).join("input");
+        if !crypt::read(path, input_path)?.has_contents() {
             display::day(year, day);
             display::no_input();
             continue;
         }
         display::day(year, day);
-        match run::build(path, false, false) {
+        let executables = match run::build(path, &options.build, false) {
+            Ok(BuildResult::Success(executables)) => executables,
             Ok(BuildResult::Failure) => {
                 display::build_error();
+                report.record(Outcome::BuildError);
                 continue;
             }
             Err(e) => {
                 display::build_error();
                 return Err(e);
             }
-            _ => (),
-        }
-        display::part("1");
-        if let Err(e) = run_part(path, year, day, "1", &mut total_time, &mut num_parts) {
-            display::run_error();
-            return Err(e);
-        }
-        if day_number < 25 {
-            display::day_part(year, day, "2");
-            if let Err(e) = run_part(path, year, day, "2", &mut total_time, &mut num_parts) {
-                display::run_error();
+        };
+        let exe = match executables.day(year, day, options.variant.as_deref()) {
+            Ok(exe) => exe,
+            Err(e) => {
+                display::build_error();
                 return Err(e);
             }
+        };
+        let format = &options.timing.format(year, day);
+        for part in ["1", "2"] {
+            if part == "2" {
+                if day_number == 25 {
+                    break;
+                }
+                display::day_part(year, day, part);
+            } else {
+                display::part(part);
+            }
+            match run_part(exe, path, part, options, format, &mut report) {
+                Ok(Some(time)) => {
+                    total_time += time;
+                    num_parts += 1;
+                }
+                Ok(None) => (),
+                Err(e) => {
+                    display::run_error();
+                    return Err(e);
+                }
+            }
         }
     }
     if total_days == 0 {
         return AocError::NoDays.err();
     }
-    display::stats(total_time, num_parts);
-    Ok(())
+    display::stats(
+        total_time,
+        num_parts,
+        &options.build.profile,
+        &options.timing.format,
+    );
+    display::summary(&report);
+    report.result()
 }
 
 fn run_part(
+    exe: Executable,
     path: &Path,
-    year: &str,
-    day: &str,
     part: &str,
-    total_time: &mut u64,
-    num_parts: &mut u8,
-) -> Result<()> {
-    match run::run(path, year, day, "actual", part, false, false)? {
-        run::RunResult::Panic => display::panic(),
+    options: &RunOptions,
+    format: &TimeFormat,
+    report: &mut Report,
+) -> Result<Option<u64>> {
+    let input = &options.actual;
+    let result = run::run(exe, path, input, part, false, options.timeout)?;
+    let correct = get_correct(path, &path.join("data").join(input), part)?;
+    report.record(Outcome::of(&result, correct.as_deref()));
+    match result {
+        run::RunResult::Panic { .. } => display::panic(),
         run::RunResult::Unimplemented => display::unimplemented(),
+        run::RunResult::Timeout => display::timeout(),
         run::RunResult::Success { answer, time } => {
-            let correct = get_correct(&path.join("data").join("actual"), part)?;
-            display::answer(&answer, correct.as_deref(), time);
+            display::timed_answer(&answer, correct.as_deref(), time, format);
             println!();
-            *total_time += time;
-            *num_parts += 1;
+            return Ok(Some(time));
         }
     };
-    Ok(())
+    Ok(None)
 }
 
-pub fn test_day(path: &Path, year: &str, day: &str, parts: Parts) -> Result<()> {
-    if !run::build(path, false, true)?.success() {
-        return Ok(());
-    }
+pub fn test_day(
+    path: &Path,
+    year: &str,
+    day: &str,
+    parts: Parts,
+    accept: Accept,
+    junit: Option<&Path>,
+    options: &RunOptions,
+) -> Result<()> {
+    let BuildResult::Success(executables) = run::build(path, &options.build, true)? else {
+        let mut report = Report::default();
+        report.record_case(Case::build_error(year, day));
+        return finish_tests(report, junit);
+    };
+    let exe = executables.day(year, day, options.variant.as_deref())?;
     let parts = &match parts {
         Parts::Default => vec!["1", "2"],
         Parts::Part(ref part) => vec![part.as_ref()],
     }[..];
-    if test_parts(path, year, day, parts)? {
-        display::info!("nothing to test");
-    }
-    Ok(())
+    let report = test_parts(exe, path, year, day, parts, accept, options)?;
+    finish_tests(report, junit)
 }
 
-fn test_parts(path: &Path, year: &str, day: &str, parts: &[&str]) -> Result<bool> {
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Accept {
+    Never,
+    Ask,
+    All,
+}
+
+fn test_parts(
+    exe: Executable,
+    path: &Path,
+    year: &str,
+    day: &str,
+    parts: &[&str],
+    accept: Accept,
+    options: &RunOptions,
+) -> Result<Report> {
+    let format = &options.timing.format(year, day);
     let mut implemented = [true, true];
-    let mut empty = true;
+    let mut report = Report::default();
     for dir in path.join("data").read_dir().context(AocError::FileRead)? {
         let dir = &dir.context(AocError::FileRead)?;
         let input = dir.file_name();
@@ -321,102 +706,330 @@ fn test_parts(path: &Path, year: &str, day: &str, parts: &[&str]) -> Result<bool
             continue;
         };
         let data_path = &dir.path();
-        if !data_path.join("input").read_file()?.has_contents() {
+        if !crypt::read(path, &data_path.join("input"))?.has_contents() {
             continue;
         }
         for (i, &part) in parts.iter().enumerate() {
             if !implemented[i] {
                 continue;
             }
-            let correct = get_correct(data_path, part)?;
-            if correct.is_none() {
+            let correct = get_correct(path, data_path, part)?;
+            if correct.is_none() && accept == Accept::Never {
                 continue;
             }
-            empty = false;
             display::day_part(year, day, part);
-            let result = run::run(path, year, day, input, part, false, false);
+            let result = run::run(exe, path, input, part, false, options.timeout);
             if result.is_err() {
                 display::run_error();
             }
-            match result? {
-                run::RunResult::Panic => display::panic_input(input),
+            let result = result?;
+            let mut case = Case::new(year, day, part, input, &result, correct.as_deref());
+            match &result {
+                run::RunResult::Panic { .. } => display::panic_input(input),
+                run::RunResult::Timeout => display::timeout_input(input),
                 run::RunResult::Unimplemented => {
                     display::unimplemented();
                     implemented[i] = false;
                 }
                 run::RunResult::Success { answer, time } => {
-                    display::answer(&answer, correct.as_deref(), time);
+                    let multiline = display::answer(answer, correct.as_deref(), *time, format);
                     println!("  ({input})");
+                    if accept != Accept::Never && case.outcome != Outcome::Correct {
+                        if multiline {
+                            println!("{answer}");
+                        }
+                        let confirmed = accept == Accept::All
+                            || display::confirm(&format!(
+                                "accept answer as correct for part {part} of input `{input}`?"
+                            ));
+                        if confirmed {
+                            let part_path = &data_path.join(part);
+                            if !part_path.try_is_dir()? {
+                                fs::create_dir(part_path).context(AocError::FileWrite)?;
+                            }
+                            let answer_path = &part_path.join("answer");
+                            crypt::write(path, answer_path, answer)?;
+                            display::success!("answer written to {}", display::path(answer_path));
+                            case.outcome = Outcome::Correct;
+                            case.expected = Some(answer.clone());
+                        }
+                    }
                 }
             }
+            report.record_case(case);
         }
     }
-    Ok(empty)
+    Ok(report)
 }
 
-pub fn test_days(path: &Path, year: &str, days: impl IntoIterator<Item = u8>) -> Result<()> {
-    let mut empty = true;
+pub fn test_days(
+    path: &Path,
+    year: &str,
+    days: impl IntoIterator<Item = u8>,
+    accept: Accept,
+    junit: Option<&Path>,
+    options: &RunOptions,
+) -> Result<()> {
+    let mut report = Report::default();
     for day_number in days {
         let day = &format!("{day_number:02}");
         let path = &path.join(day);
-        if !path.try_is_dir()? || !run::build(path, false, false)?.success() {
+        if !path.try_is_dir()? {
             continue;
         }
-        if !test_parts(path, year, day, &["1", "2"])? {
-            empty = false;
-        }
+        let BuildResult::Success(executables) = run::build(path, &options.build, false)? else {
+            display::day(year, day);
+            display::build_error();
+            report.record_case(Case::build_error(year, day));
+            continue;
+        };
+        report.merge(test_parts(
+            executables.day(year, day, options.variant.as_deref())?,
+            path,
+            year,
+            day,
+            &["1", "2"],
+            accept,
+            options,
+        )?);
     }
-    if empty {
+    finish_tests(report, junit)
+}
+
+fn finish_tests(report: Report, junit: Option<&Path>) -> Result<()> {
+    if let Some(junit) = junit {
+        junit::write(junit, &report).context(AocError::FileWrite)?;
+        display::success!("JUnit report written to {}", display::path(junit));
+    }
+    if report.is_empty() {
         display::info!("nothing to test");
+        return Ok(());
+    }
+    display::summary(&report);
+    report.result()
+}
+
+pub fn compare(
+    path: &Path,
+    year: &str,
+    day: &str,
+    parts: Parts,
+    options: &RunOptions,
+) -> Result<()> {
+    let BuildResult::Success(executables) = run::build(path, &options.build, true)? else {
+        return Ok(());
+    };
+    let variants = executables.variants(year, day);
+    if variants.len() < 2 {
+        display::info!("no variants to compare");
+        return Ok(());
+    }
+    let parts = &match parts {
+        Parts::Default => vec!["1", "2"],
+        Parts::Part(ref part) => vec![part.as_ref()],
+    }[..];
+    let mut inputs = Vec::new();
+    for dir in path.join("data").read_dir().context(AocError::FileRead)? {
+        let dir = dir.context(AocError::FileRead)?;
+        let Ok(input) = dir.file_name().into_string() else {
+            continue;
+        };
+        if crypt::read(path, &dir.path().join("input"))?.has_contents() {
+            inputs.push(input);
+        }
+    }
+    inputs.sort_by(|a, b| (a != "actual", a).cmp(&(b != "actual", b)));
+    let format = &options.timing.format(year, day);
+    let label_width = inputs
+        .iter()
+        .map(|input| input.len() + 2)
+        .max()
+        .unwrap_or(0);
+    let names: Vec<_> = variants.iter().map(|(name, _)| *name).collect();
+    display::compare_header(label_width, &names);
+    let mut disagreements = 0;
+    for input in &inputs {
+        let data_path = &path.join("data").join(input);
+        for &part in parts {
+            let mut results = Vec::with_capacity(variants.len());
+            for &(_, exe) in variants.iter().rev() {
+                results.push(run::run(exe, path, input, part, false, options.timeout)?);
+            }
+            results.reverse();
+            if results
+                .iter()
+                .all(|result| matches!(result, run::RunResult::Unimplemented))
+            {
+                continue;
+            }
+            let correct = get_correct(path, data_path, part)?;
+            let label = format!("{input}/{part}");
+            if !display::compare_row(
+                &label,
+                label_width,
+                &names,
+                &results,
+                correct.as_deref(),
+                format,
+            ) {
+                disagreements += 1;
+            }
+        }
+    }
+    if disagreements > 0 {
+        return AocError::Disagreements(disagreements).err();
+    }
+    Ok(())
+}
+
+pub fn get(path: &Path, year: &str, day: &str, account: &Account, refresh: bool) -> Result<()> {
+    get_day(path, year, day, account, refresh, None)
+}
+
+pub fn get_days(
+    root: &Path,
+    year: &str,
+    days: Option<Vec<u8>>,
+    account: &Account,
+    refresh: bool,
+    new: Option<&BuildOptions>,
+) -> Result<()> {
+    let client = &client(root, account)?;
+    let completion = client.get_year_completion(year, false)?;
+    let solved: Vec<u8> = (1..=25)
+        .filter(|&day| !matches!(completion.days[day as usize - 1], DayCompletion::None))
+        .filter(|day| days.as_ref().is_none_or(|days| days.contains(day)))
+        .collect();
+    if solved.is_empty() {
+        display::info!("no solved days to download");
+        return Ok(());
+    }
+    let mut synced = 0;
+    let mut failures = 0;
+    for day_number in solved {
+        let day = &format!("{day_number:02}");
+        let path = &root.join(year).join(day);
+        if !path.join("Cargo.toml").try_is_file()? {
+            let Some(options) = new else {
+                display::info!("skipping {year}/{day}, no day directory found");
+                continue;
+            };
+            if new_day(path, year, day, options).display_err().is_none() {
+                failures += 1;
+                continue;
+            }
+        }
+        display::info!("downloading {year}/{day}...");
+        if get_day(path, year, day, account, refresh, Some(client))
+            .display_err()
+            .is_some()
+        {
+            synced += 1;
+        } else {
+            failures += 1;
+        }
+    }
+    display::success!("synced {synced} day(s) of {year}");
+    if failures > 0 {
+        return AocError::GetFailures(failures).err();
     }
     Ok(())
 }
 
-pub fn get(path: &Path, year: &str, day: &str) -> Result<()> {
+fn get_day(
+    path: &Path,
+    year: &str,
+    day: &str,
+    account: &Account,
+    refresh: bool,
+    client: Option<&Client>,
+) -> Result<()> {
     const PARTS: [&str; 2] = ["1", "2"];
-    let data_path = &path.join("data").join("actual");
+    let data_path = &path.join("data").join(account.input());
     let input_path = &data_path.join("input");
     let answer_paths = &PARTS.map(|part| data_path.join(part).join("answer"));
 
-    let update_input = !input_path.read_file()?.has_contents();
+    let local_input = crypt::read(path, input_path)?.get_contents();
+    let local_answers = [
+        crypt::read(path, &answer_paths[0])?.get_contents(),
+        crypt::read(path, &answer_paths[1])?.get_contents(),
+    ];
+    let update_input = refresh || local_input.is_none();
     let update_answers = [
-        !answer_paths[0].read_file()?.has_contents(),
-        day != "25" && !answer_paths[1].read_file()?.has_contents(),
+        refresh || local_answers[0].is_none(),
+        day != "25" && (refresh || local_answers[1].is_none()),
     ];
 
     if update_input || update_answers[0] || update_answers[1] {
-        let session = &get_session(path.parent().unwrap().parent().unwrap())?;
+        let owned;
+        let client = match client {
+            Some(client) => client,
+            None => {
+                owned = self::client(path.parent().unwrap().parent().unwrap(), account)?;
+                &owned
+            }
+        };
         if !data_path.try_is_dir()? {
             fs::create_dir_all(data_path).context(AocError::FileWrite)?;
         }
         if update_input {
             display::info!("downloading puzzle input...");
-            let input = network::get_input(year, day, session)?;
-            fs::write(input_path, input).context(AocError::FileWrite)?;
-            display::success!("input file written to {}", display::path(input_path));
+            let input = client.get_input(year, day)?;
+            let write = match &local_input {
+                Some(local) if hash(local.trim_end()) == hash(&input) => {
+                    display::info!("input is up to date");
+                    false
+                }
+                Some(_) => {
+                    display::info!("local input differs from the site");
+                    display::confirm("overwrite the local input?")
+                }
+                None => true,
+            };
+            if write {
+                crypt::write(path, input_path, &input)?;
+                display::success!("input file written to {}", display::path(input_path));
+            }
         }
         if update_answers[0] || update_answers[1] {
             display::info!("downloading puzzle answers...");
-            let progress = network::get_progress(year, day, session)?;
+            let progress = client.get_progress(year, day, false)?;
             let answers = [progress.part_1, progress.part_2];
             for i in 0..2 {
                 if !update_answers[i] {
                     continue;
                 }
                 let part = PARTS[i];
-                if let Some(answer) = &answers[i] {
-                    let part_path = answer_paths[i].parent().unwrap();
-                    if !part_path.try_is_dir()? {
-                        fs::create_dir(part_path).context(AocError::FileWrite)?;
-                    }
-                    fs::write(&answer_paths[i], answer).context(AocError::FileWrite)?;
-                    display::success!(
-                        "answer to part {part} written to {}",
-                        display::path(&answer_paths[i])
-                    );
-                } else {
+                let Some(answer) = &answers[i] else {
                     display::info!("no answer to part {part} found");
+                    continue;
+                };
+                let write = match &local_answers[i] {
+                    Some(local) if local.trim() == answer => {
+                        display::info!("answer to part {part} is up to date");
+                        false
+                    }
+                    Some(local) => {
+                        display::info!(
+                            "local answer to part {part} `{}` differs from the site's `{answer}`",
+                            local.trim()
+                        );
+                        display::confirm(&format!("overwrite the answer to part {part}?"))
+                    }
+                    None => true,
+                };
+                if !write {
+                    continue;
                 }
+                let part_path = answer_paths[i].parent().unwrap();
+                if !part_path.try_is_dir()? {
+                    fs::create_dir(part_path).context(AocError::FileWrite)?;
+                }
+                crypt::write(path, &answer_paths[i], answer)?;
+                display::success!(
+                    "answer to part {part} written to {}",
+                    display::path(&answer_paths[i])
+                );
             }
         }
     } else {
@@ -426,12 +1039,24 @@ pub fn get(path: &Path, year: &str, day: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn submit(path: &Path, year: &str, day: &str, answer: Option<&str>) -> Result<()> {
-    let session = &get_session(path.parent().unwrap().parent().unwrap())?;
+fn hash(text: &str) -> Vec<u8> {
+    digest::digest(&digest::SHA256, text.as_bytes())
+        .as_ref()
+        .to_vec()
+}
+
+pub fn submit(
+    path: &Path,
+    year: &str,
+    day: &str,
+    answer: Option<&str>,
+    account: &Account,
+) -> Result<()> {
+    let client = &client(path.parent().unwrap().parent().unwrap(), account)?;
     display::info!("getting progress");
-    let progress = network::get_progress(year, day, session)?;
+    let progress = client.get_progress(year, day, false)?;
     if let Some(part) = &progress.next {
-        let answer_path = &path.join("data").join("actual").join(part);
+        let answer_path = &path.join("data").join(account.input()).join(part);
         let answer = &if let Some(answer) = answer {
             answer.to_string()
         } else {
@@ -446,14 +1071,15 @@ pub fn submit(path: &Path, year: &str, day: &str, answer: Option<&str>) -> Resul
             fs::create_dir_all(answer_path).context(AocError::FileWrite)?;
         }
         display::day_part(year, day, part);
-        let result = network::submit(year, day, part, answer, session);
+        let result = client.submit(year, day, part, answer);
         if result.is_err() {
             display::submit_error();
         }
         match result? {
             network::SubmissionResult::Correct => {
                 display::just_answer(answer, true);
-                fs::write(answer_path.join("answer"), answer).context(AocError::FileWrite)?;
+                let answer_path = &answer_path.join("answer");
+                crypt::write(path, answer_path, answer)?;
             }
             network::SubmissionResult::Wait => {
                 display::wait();
@@ -478,26 +1104,40 @@ pub fn open_day(year: &str, day: &str) -> Result<()> {
         .context(AocError::Browser)
 }
 
-pub fn all_progress(path: &Path) -> Result<()> {
-    let session = &get_session(path)?;
-    let year_completion = network::get_year_completion("2015", session)?;
+pub fn all_progress(path: &Path, account: &Account) -> Result<()> {
+    let client = &client(path, account)?;
+    let events = client.get_events().display_err().unwrap_or_else(|| {
+        display::info!("falling back to event years by date");
+        date::event_years()
+            .into_iter()
+            .map(|year| Event { year, stars: None })
+            .collect()
+    });
     println!();
     display::completion_header();
-    display::year_completion("2015", year_completion);
-    let mut year = 2016;
-    let mut year_string = "2016".to_string();
-    while let Ok(year_completion) = network::get_year_completion(&year_string, session) {
-        display::year_completion(&year_string, year_completion);
-        year += 1;
-        year_string = year.to_string();
+    let mut error = None;
+    for Event { year, stars } in &events {
+        let year_completion = if *stars == Some(0) {
+            YearCompletion::default()
+        } else {
+            match client.get_year_completion(year, true) {
+                Ok(year_completion) => year_completion,
+                Err(e) => {
+                    display::year_completion_error(year);
+                    error = Some(e);
+                    continue;
+                }
+            }
+        };
+        display::year_completion(year, year_completion);
     }
     println!();
-    Ok(())
+    error.map_or(Ok(()), Err)
 }
 
-pub fn year_progress(path: &Path, year: &str) -> Result<()> {
-    let session = &get_session(path)?;
-    let year_completion = network::get_year_completion(year, session)?;
+pub fn year_progress(path: &Path, year: &str, account: &Account) -> Result<()> {
+    let client = &client(path, account)?;
+    let year_completion = client.get_year_completion(year, true)?;
     println!();
     display::completion_header();
     display::year_completion(year, year_completion);
@@ -505,9 +1145,9 @@ pub fn year_progress(path: &Path, year: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn day_progress(path: &Path, year: &str, day: &str) -> Result<()> {
-    let session = &get_session(path)?;
-    let progress = network::get_progress(year, day, session)?;
+pub fn day_progress(path: &Path, year: &str, day: &str, account: &Account) -> Result<()> {
+    let client = &client(path, account)?;
+    let progress = client.get_progress(year, day, true)?;
     display::day_part(year, day, "1");
     if let Some(answer) = &progress.part_1 {
         display::just_answer(answer, true);
@@ -521,6 +1161,153 @@ pub fn day_progress(path: &Path, year: &str, day: &str) -> Result<()> {
     Ok(())
 }
 
+pub fn leaderboard(
+    root: &Path,
+    id: &str,
+    year: &str,
+    day: Option<&str>,
+    account: &Account,
+) -> Result<()> {
+    let client = &client(root, account)?;
+    let leaderboard = client.get_leaderboard(year, id)?;
+    let mut members: Vec<_> = leaderboard.members.values().collect();
+    if members.is_empty() {
+        display::info!("leaderboard has no members");
+        return Ok(());
+    }
+    println!();
+    let Some(day) = day else {
+        members.sort_by_key(|member| {
+            (
+                Reverse(member.local_score),
+                Reverse(member.stars),
+                member.last_star_ts,
+            )
+        });
+        display::leaderboard_header();
+        for (rank, member) in members.iter().enumerate() {
+            display::leaderboard_row(
+                rank + 1,
+                member.local_score,
+                member.completion(),
+                &member.display_name(),
+            );
+        }
+        println!();
+        return Ok(());
+    };
+    let day_num = day.parse::<u8>().unwrap();
+    let unlock = date::unlock_time(year.parse().unwrap(), day_num);
+    let times = |member: &Member| {
+        [1, 2].map(|part| member.star_time(day_num, part).map(|time| time - unlock))
+    };
+    members.retain(|member| times(member)[0].is_some());
+    if members.is_empty() {
+        display::info!("no stars collected on {year}/{day}");
+        return Ok(());
+    }
+    members.sort_by_key(|member| {
+        let [part_1, part_2] = times(member);
+        (part_2.is_none(), part_2, part_1)
+    });
+    display::leaderboard_day_header(year, day);
+    for (rank, member) in members.iter().enumerate() {
+        display::leaderboard_day_row(rank + 1, times(member), &member.display_name());
+    }
+    println!();
+    Ok(())
+}
+
+pub fn stats(root: &Path, year: &str, account: &Account) -> Result<()> {
+    let client = &client(root, account)?;
+    let stats = client.get_stats(year)?;
+    if stats.is_empty() {
+        display::info!("no stars collected in {year}");
+        return Ok(());
+    }
+    println!();
+    display::stats_header(year);
+    for day in &stats {
+        display::stats_row(day.day, &day.parts);
+    }
+    println!();
+    let stars = stats
+        .iter()
+        .map(|day| day.parts.iter().flatten().count())
+        .sum::<usize>();
+    let score: u32 = stats
+        .iter()
+        .flat_map(|day| day.parts.iter().flatten())
+        .map(|part| part.score)
+        .sum();
+    display::info!("{stars} star(s), {score} global score");
+    Ok(())
+}
+
+pub fn calendar(root: &Path, year: &str, account: &Account) -> Result<()> {
+    let client = &client(root, account)?;
+    let calendar = client.get_calendar(year)?;
+    display::calendar(year, &calendar);
+    Ok(())
+}
+
+pub fn session_check(root: &Path, account: &Account) -> Result<()> {
+    let (session, source) = account.session_source(root)?;
+    let user = Client::new(root, session)?.get_user()?;
+    display::success!("logged in as {user} with the cookie from {source}");
+    let account::Source::File(path) = &source else {
+        return Ok(());
+    };
+    let age = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok());
+    if let Some(age) = age {
+        display::info!(
+            "cookie saved {} day(s) ago, the site does not report when it expires",
+            age.as_secs() / 86_400
+        );
+    }
+    Ok(())
+}
+
+pub fn session_set(root: &Path, account: &Account) -> Result<()> {
+    let Some(cookie) = display::secret("session cookie:") else {
+        return AocError::SessionPrompt.err();
+    };
+    let path = &account.set_session(root, &cookie)?;
+    display::success!("saved session cookie to {}", display::path(path));
+    Ok(())
+}
+
+pub fn status(root: &Path, year: Option<&str>, account: &Account) -> Result<()> {
+    let mut years: Vec<(String, [Option<inputs::DayStatus>; 25])> = Vec::new();
+    for day_dir in file::day_dirs(root)? {
+        if year.is_some_and(|year| year != day_dir.year) {
+            continue;
+        }
+        if years.last().is_none_or(|(year, _)| *year != day_dir.year) {
+            years.push((day_dir.year.clone(), Default::default()));
+        }
+        let day = day_dir.day.parse::<usize>().unwrap();
+        years.last_mut().unwrap().1[day - 1] = Some(inputs::day_status(
+            &day_dir.path,
+            &day_dir.day,
+            &account.input(),
+        )?);
+    }
+    if years.is_empty() {
+        display::info!("no day directories found");
+        return Ok(());
+    }
+    println!();
+    for (year, days) in &years {
+        display::year_status(year, days);
+        println!();
+    }
+    Ok(())
+}
+
 pub fn clean_year(path: &Path) -> Result<()> {
     let mut empty = true;
     for day in 1..=25 {
@@ -540,54 +1327,192 @@ pub fn clean_year(path: &Path) -> Result<()> {
 }
 
 pub fn clean_day(path: &Path, silent: bool) -> Result<()> {
-    let data_path = &path.join("data").join("actual");
-    let input_path = &data_path.join("input");
-    if input_path.try_is_file()? {
-        fs::write(input_path, "").context(AocError::FileWrite)?;
-        if !silent {
-            display::success!("reset input file to empty");
-        }
-    } else if !silent {
-        display::info!("no input file found");
-    }
-    for part in ["1", "2"] {
-        let part_path = data_path.join(part).join("answer");
-        if part_path.try_is_file()? {
-            fs::write(part_path, "").context(AocError::FileWrite)?;
+    let mut inputs = crypt::secret_inputs(path)?;
+    if !inputs.iter().any(|input| input == "actual") {
+        inputs.insert(0, "actual".to_string());
+    }
+    for input in &inputs {
+        let data_path = &path.join("data").join(input);
+        let input_path = &data_path.join("input");
+        if input_path.try_is_file()? || crypt::sealed_path(input_path).try_is_file()? {
+            fs::write(input_path, "").context(AocError::FileWrite)?;
             if !silent {
-                display::success!("reset part {part} answer file to empty");
+                display::success!("reset `{input}` input file to empty");
             }
         } else if !silent {
-            display::info!("no part {part} answer file found");
+            display::info!("no `{input}` input file found");
+        }
+        for part in ["1", "2"] {
+            let part_path = &data_path.join(part).join("answer");
+            if part_path.try_is_file()? || crypt::sealed_path(part_path).try_is_file()? {
+                fs::write(part_path, "").context(AocError::FileWrite)?;
+                if !silent {
+                    display::success!("reset `{input}` part {part} answer file to empty");
+                }
+            } else if !silent {
+                display::info!("no `{input}` part {part} answer file found");
+            }
+            let out_path = &data_path.join(part).join("out");
+            if out_path.try_is_dir()? {
+                fs::remove_dir_all(out_path).context(AocError::FileWrite)?;
+            }
+        }
+        for path in [
+            input_path,
+            &data_path.join("1").join("answer"),
+            &data_path.join("2").join("answer"),
+        ] {
+            let sealed_path = &crypt::sealed_path(path);
+            if sealed_path.try_is_file()? {
+                fs::remove_file(sealed_path).context(AocError::FileWrite)?;
+            }
         }
     }
     Ok(())
 }
 
+pub fn lock(root: &Path) -> Result<()> {
+    let key = &crypt::Key::load_or_create(root)?;
+    add_gitignore_lines(
+        root,
+        &[
+            "/.key",
+            "**/data/actual*/input",
+            "**/data/actual*/[1-2]/answer",
+        ],
+    )?;
+    let mut count = 0;
+    for day in file::day_dirs(root)? {
+        for path in crypt::secret_files(&day.path)? {
+            if path.try_is_file()? {
+                crypt::seal(key, &path)?;
+                fs::remove_file(&path).context(AocError::FileWrite)?;
+                count += 1;
+            }
+        }
+    }
+    display::success!("encrypted {count} input and answer file(s)");
+    let tracked = git_files(root, &["ls-files", "-z"])?;
+    let tracked = tracked
+        .iter()
+        .filter(|file| is_plaintext_secret(file))
+        .count();
+    if tracked > 0 {
+        display::info!(
+            "{tracked} plaintext file(s) are still tracked by git, \
+            remove them from the index with `git rm --cached`"
+        );
+    }
+    Ok(())
+}
+
+pub fn unlock(root: &Path) -> Result<()> {
+    let key = &crypt::Key::load(root)?.ok_or(AocError::NoKey)?;
+    let mut count = 0;
+    for day in file::day_dirs(root)? {
+        for path in crypt::secret_files(&day.path)? {
+            if !path.try_is_file()? && crypt::sealed_path(&path).try_is_file()? {
+                crypt::open(key, &path)?;
+                count += 1;
+            }
+        }
+    }
+    display::success!("decrypted {count} input and answer file(s)");
+    Ok(())
+}
+
+pub fn check(root: &Path) -> Result<()> {
+    let staged = git_files(root, &["diff", "--cached", "--name-only", "-z"])?;
+    let staged: Vec<_> = staged
+        .into_iter()
+        .filter(|file| is_plaintext_secret(file))
+        .collect();
+    if staged.is_empty() {
+        display::success!("no plaintext inputs or answers staged");
+        return Ok(());
+    }
+    staged
+        .join(", ")
+        .error()
+        .context(AocError::PlaintextStaged)
+        .err()
+}
+
+pub fn install_hook(root: &Path) -> Result<()> {
+    let hooks = &root.join(".git").join("hooks");
+    if !hooks.try_is_dir()? {
+        return AocError::NoGitRepository.err();
+    }
+    let hook = &hooks.join("pre-commit");
+    if hook.try_exists().context(AocError::FileRead)? {
+        return AocError::PathExists(display::path(hook)).err();
+    }
+    fs::write(hook, "#!/bin/sh\nexec aoc check\n").context(AocError::FileWrite)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(hook, fs::Permissions::from_mode(0o755))
+            .context(AocError::FileWrite)?;
+    }
+    display::success!("installed pre-commit hook at {}", display::path(hook));
+    Ok(())
+}
+
+fn git_files(root: &Path, args: &[&str]) -> Result<Vec<String>> {
+    let output = process::Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .context(AocError::Git)?;
+    if !output.status.success() {
+        return String::from_utf8_lossy(&output.stderr)
+            .trim()
+            .error()
+            .context(AocError::Git)
+            .err();
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|file| !file.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+fn is_plaintext_secret(file: &str) -> bool {
+    let re = Regex::new(r"(^|/)data/actual(-[^/]*)?/(input|[12]/answer)$").unwrap();
+    re.is_match(file)
+}
+
+fn add_gitignore_lines(root: &Path, lines: &[&str]) -> Result<()> {
+    let path = &root.join(".gitignore");
+    let mut contents = path.read_file()?.get_contents().unwrap_or_default();
+    let mut added = false;
+    for &line in lines {
+        if !contents.lines().any(|existing| existing.trim() == line) {
+            if !contents.is_empty() && !contents.ends_with('\n') {
+                contents.push('\n');
+            }
+            contents.push_str(line);
+            contents.push('\n');
+            added = true;
+        }
+    }
+    if added {
+        fs::write(path, contents).context(AocError::FileWrite)?;
+        display::success!("updated `.gitignore`");
+    }
+    Ok(())
+}
+
 pub fn help() -> Result<()> {
     webbrowser::open("https://github.com/sncxyz/aocli/blob/master/README.md#commands")
         .context(AocError::Browser)
 }
 
-fn get_session(root: &Path) -> Result<String> {
-    root.join(".session")
-        .read_file()
-        .and_then(FileInfo::try_contents)
-        .map(|contents| {
-            let trimmed = contents.trim();
-            if trimmed.starts_with("session=") {
-                trimmed.to_owned()
-            } else {
-                format!("session={trimmed}")
-            }
-        })
-        .context("failed to get session cookie")
+fn client(root: &Path, account: &Account) -> Result<Client> {
+    Client::new(root, account.session(root)?)
 }
 
-fn get_correct(data_path: &Path, part: &str) -> Result<Option<String>> {
-    Ok(data_path
-        .join(part)
-        .join("answer")
-        .read_file()?
-        .get_contents())
+fn get_correct(path: &Path, data_path: &Path, part: &str) -> Result<Option<String>> {
+    Ok(crypt::read(path, &data_path.join(part).join("answer"))?.get_contents())
 }
@@ -0,0 +1,67 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    error::{AocError, Context, Result},
+    file::PathInfo,
+};
+
+pub const CONFIG: &str = "aoc.toml";
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub build: BuildConfig,
+    pub run: RunConfig,
+    pub network: NetworkConfig,
+    pub timing: TimingConfig,
+    pub session_command: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct BuildConfig {
+    pub profile: Option<String>,
+    pub features: Vec<String>,
+    pub rustflags: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunConfig {
+    pub timeout: Option<f64>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    pub timeout: Option<f64>,
+    pub retries: Option<u32>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimingConfig {
+    pub fast: Option<f64>,
+    pub slow: Option<f64>,
+    pub precision: Option<usize>,
+    pub unit: Option<String>,
+    pub days: HashMap<String, DayTimingConfig>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct DayTimingConfig {
+    pub fast: Option<f64>,
+    pub slow: Option<f64>,
+}
+
+impl Config {
+    pub fn load(root: &Path) -> Result<Self> {
+        let Some(contents) = root.join(CONFIG).read_file()?.get_contents() else {
+            return Ok(Self::default());
+        };
+        toml::from_str(&contents).context(AocError::Config)
+    }
+}
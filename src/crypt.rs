@@ -0,0 +1,189 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ring::{
+    aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+
+use crate::{
+    display,
+    error::{AocError, Context, Result, ToErr},
+    file::{self, FileInfo, PathInfo},
+};
+
+pub const KEY: &str = ".key";
+const EXTENSION: &str = "enc";
+const KEY_LEN: usize = 32;
+
+pub struct Key(LessSafeKey);
+
+impl Key {
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let Some(contents) = root.join(KEY).read_file()?.get_contents() else {
+            return Ok(None);
+        };
+        let bytes = decode_hex(contents.trim()).ok_or(AocError::Key)?;
+        Self::from_bytes(&bytes).map(Some)
+    }
+
+    pub fn load_or_create(root: &Path) -> Result<Self> {
+        if let Some(key) = Self::load(root)? {
+            return Ok(key);
+        }
+        let mut bytes = [0; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| AocError::Key)?;
+        let path = &root.join(KEY);
+        file::write_private(path, encode_hex(&bytes)).context(AocError::FileWrite)?;
+        display::success!("generated encryption key at {}", display::path(path));
+        display::info!("keep a copy of the key somewhere safe outside the workspace");
+        Self::from_bytes(&bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let key = UnboundKey::new(&CHACHA20_POLY1305, bytes).map_err(|_| AocError::Key)?;
+        Ok(Self(LessSafeKey::new(key)))
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| AocError::Encrypt)?;
+        let mut data = plaintext.to_vec();
+        self.0
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+            .map_err(|_| AocError::Encrypt)?;
+        let mut sealed = nonce.to_vec();
+        sealed.append(&mut data);
+        Ok(sealed)
+    }
+
+    fn decrypt(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        if sealed.len() < NONCE_LEN + aead::MAX_TAG_LEN {
+            return AocError::Decrypt.err();
+        }
+        let (nonce, data) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| AocError::Decrypt)?;
+        let mut data = data.to_vec();
+        let plaintext = self
+            .0
+            .open_in_place(nonce, Aad::empty(), &mut data)
+            .map_err(|_| AocError::Decrypt)?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+pub fn sealed_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(EXTENSION);
+    path.with_file_name(name)
+}
+
+pub fn is_secret_input(input: &str) -> bool {
+    input == "actual" || input.starts_with("actual-")
+}
+
+pub fn secret_inputs(day_path: &Path) -> Result<Vec<String>> {
+    let data_path = &day_path.join("data");
+    let mut inputs = Vec::new();
+    if !data_path.try_is_dir()? {
+        return Ok(inputs);
+    }
+    for dir in data_path.read_dir().context(AocError::FileRead)? {
+        let dir = dir.context(AocError::FileRead)?;
+        let Ok(input) = dir.file_name().into_string() else {
+            continue;
+        };
+        if is_secret_input(&input) && dir.path().try_is_dir()? {
+            inputs.push(input);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+pub fn secret_files(day_path: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for input in secret_inputs(day_path)? {
+        let path = day_path.join("data").join(input);
+        files.push(path.join("input"));
+        files.push(path.join("1").join("answer"));
+        files.push(path.join("2").join("answer"));
+    }
+    Ok(files)
+}
+
+pub fn seal(key: &Key, path: &Path) -> Result<()> {
+    let plaintext = fs::read(path).context(AocError::FileRead)?;
+    seal_contents(key, path, &plaintext)
+}
+
+fn seal_contents(key: &Key, path: &Path, plaintext: &[u8]) -> Result<()> {
+    let sealed_path = &sealed_path(path);
+    if sealed_path.try_is_file()? && decrypt(key, path)? == plaintext {
+        return Ok(());
+    }
+    let sealed = key
+        .encrypt(plaintext)
+        .with_context(|| display::path(path))?;
+    fs::write(sealed_path, sealed).context(AocError::FileWrite)
+}
+
+fn decrypt(key: &Key, path: &Path) -> Result<Vec<u8>> {
+    let sealed_path = &sealed_path(path);
+    let sealed = fs::read(sealed_path).context(AocError::FileRead)?;
+    key.decrypt(&sealed)
+        .with_context(|| display::path(sealed_path))
+}
+
+pub fn open(key: &Key, path: &Path) -> Result<()> {
+    let plaintext = decrypt(key, path)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(AocError::FileWrite)?;
+    }
+    fs::write(path, plaintext).context(AocError::FileWrite)
+}
+
+pub fn read<'a>(day_path: &Path, path: &'a Path) -> Result<FileInfo<'a>> {
+    if path.try_is_file()? || !sealed_path(path).try_is_file()? {
+        return Path::read_file(path);
+    }
+    let key = &Key::load(root(day_path))?.ok_or(AocError::NoKey)?;
+    let plaintext = String::from_utf8(decrypt(key, path)?).context(AocError::Decrypt)?;
+    Ok(FileInfo::from_contents(path, &plaintext))
+}
+
+pub fn write(day_path: &Path, path: &Path, contents: &str) -> Result<()> {
+    let key = match Key::load(root(day_path))? {
+        Some(key) if secret_files(day_path)?.iter().any(|file| file == path) => key,
+        _ => return fs::write(path, contents).context(AocError::FileWrite),
+    };
+    if path.try_is_file()? {
+        fs::write(path, contents).context(AocError::FileWrite)?;
+    }
+    seal_contents(&key, path, contents.as_bytes())
+}
+
+fn root(day_path: &Path) -> &Path {
+    day_path.parent().unwrap().parent().unwrap()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
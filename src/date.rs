@@ -0,0 +1,63 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const FIRST_YEAR: u16 = 2015;
+const UTC_OFFSET_SECS: i64 = -5 * 60 * 60;
+
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+}
+
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
+
+pub fn today() -> Date {
+    from_days((now() + UTC_OFFSET_SECS).div_euclid(86_400))
+}
+
+pub fn latest_event_year() -> u16 {
+    let today = today();
+    if today.month == 12 {
+        today.year
+    } else {
+        today.year - 1
+    }
+}
+
+pub fn event_years() -> Vec<String> {
+    (FIRST_YEAR..=latest_event_year())
+        .map(|year| year.to_string())
+        .collect()
+}
+
+pub fn unlock_time(year: u16, day: u8) -> i64 {
+    to_days(year as i64, 12, day as i64) * 86_400 - UTC_OFFSET_SECS
+}
+
+fn to_days(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn from_days(days: i64) -> Date {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    Date {
+        year: year as u16,
+        month: month as u8,
+    }
+}
@@ -1,16 +1,44 @@
 use std::{
     env, fmt,
-    io::{self, Write},
+    io::{self, IsTerminal, Write},
     path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicBool, Ordering},
 };
 
-use colored::{ColoredString, Colorize};
+use colored::{Color, ColoredString, Colorize};
 
-use crate::network::{DayCompletion, YearCompletion};
+use crate::{
+    inputs::{DayStatus, FileState, InputStatus},
+    network::{Calendar, CalendarKind, DayCompletion, PartStats, YearCompletion},
+    report::{Outcome, Report},
+    run::RunResult,
+};
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+pub fn set_style(color: Option<bool>, ascii: bool) {
+    let color = color.unwrap_or_else(|| {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let force = env::var_os("CLICOLOR_FORCE").is_some_and(|value| value != "0");
+        force || (!no_color && io::stdout().is_terminal() && io::stderr().is_terminal())
+    });
+    colored::control::set_override(color);
+    let ascii = ascii || env::var_os("AOC_ASCII").is_some_and(|value| !value.is_empty());
+    ASCII.store(ascii, Ordering::Relaxed);
+}
+
+fn glyph(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if ASCII.load(Ordering::Relaxed) {
+        ascii
+    } else {
+        unicode
+    }
+}
 
 fn log(header: ColoredString, message: impl fmt::Display) {
     let len = header.len();
-    let padding = if len >= 9 { 0 } else { 9 - len };
+    let padding = 9_usize.saturating_sub(len);
     eprintln!(
         "{}{}{}{}",
         " ".repeat(padding),
@@ -62,44 +90,102 @@ pub(crate) fn or(message: String) {
     log("or".normal(), format!("aoc {message}"));
 }
 
-pub fn answer(got: &str, expected: Option<&str>, time: u64) -> bool {
+pub fn problem(message: &str) {
+    log("problem".red().bold(), message);
+}
+
+pub fn fixed(message: &str) {
+    log("fixed".green().bold(), message);
+}
+
+pub fn confirm(message: &str) -> bool {
+    eprint!(
+        "{}{}{} {} ",
+        "  confirm".cyan().bold(),
+        ": ".dimmed(),
+        message,
+        "[y/N]".dimmed()
+    );
+    let _ = io::stderr().flush();
+    let mut response = String::new();
+    if io::stdin().read_line(&mut response).is_err() {
+        return false;
+    }
+    matches!(response.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+pub fn secret(message: &str) -> Option<String> {
+    eprint!("{}{}{} ", "    input".cyan().bold(), ": ".dimmed(), message);
+    let _ = io::stderr().flush();
+    let hidden = set_echo(false);
+    let mut response = String::new();
+    let read = io::stdin().read_line(&mut response);
+    if hidden {
+        set_echo(true);
+    }
+    if hidden || !io::stdin().is_terminal() {
+        eprintln!();
+    }
+    read.ok()
+        .filter(|&read| read > 0)
+        .map(|_| response.trim().to_string())
+}
+
+fn set_echo(echo: bool) -> bool {
+    process::Command::new("stty")
+        .arg(if echo { "echo" } else { "-echo" })
+        .stdin(process::Stdio::inherit())
+        .stderr(process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+pub fn answer(got: &str, expected: Option<&str>, time: u64, format: &TimeFormat) -> bool {
+    let multiline = answer_brackets(got, expected);
+    print!("  {}", colored_time(time, format));
+    multiline
+}
+
+pub fn timed_answer(got: &str, expected: Option<&str>, time: u64, format: &TimeFormat) -> bool {
+    let text = pad_left(&display_time(time, format), TIME_WIDTH);
+    print!("{}  ", color_time(text, time, format));
+    answer_brackets(got, expected)
+}
+
+fn answer_brackets(got: &str, expected: Option<&str>) -> bool {
     let got = Answer::new(got);
-    let time = colored_time(time);
     if let Some(expected) = expected {
         let expected = Answer::new(expected);
         if got.answer == expected.answer {
             print!(
-                "{}{}{}{}",
+                "{}{}{}",
                 "[".dimmed(),
                 got.display().green().bold(),
-                "]  ".dimmed(),
-                time
+                "]".dimmed()
             );
         } else {
             let (got, symbol) = if got.is_multiline && !expected.is_multiline {
                 (got.display().yellow().bold(), "-")
             } else {
-                (got.display().red().bold(), "✕")
+                (got.display().red().bold(), glyph("✕", "x"))
             };
             print!(
-                "{}{}{}{}{}{}{}{}",
+                "{}{}{}{}{}{}{}",
                 "[".dimmed(),
                 got,
                 "] ".dimmed(),
                 symbol.dimmed(),
                 " [".dimmed(),
                 expected.display().green().bold(),
-                "]  ".dimmed(),
-                time
+                "]".dimmed()
             );
         }
     } else {
         print!(
-            "{}{}{}{}",
+            "{}{}{}",
             "[".dimmed(),
             got.display().yellow().bold(),
-            "]  ".dimmed(),
-            time
+            "]".dimmed()
         );
     }
     got.is_multiline
@@ -130,9 +216,10 @@ pub fn answer_full(
     got: &str,
     expected: Option<&str>,
     time: u64,
+    format: &TimeFormat,
 ) {
     day_part(year, day, part);
-    if answer(got, expected, time) {
+    if answer(got, expected, time, format) {
         println!();
         println!("{got}");
     } else {
@@ -152,6 +239,14 @@ pub fn panic_input(input: &str) {
     println!("{}  ({})", "panic".red(), input);
 }
 
+pub fn timeout() {
+    println!("{}", "timeout".red());
+}
+
+pub fn timeout_input(input: &str) {
+    println!("{}  ({})", "timeout".red(), input);
+}
+
 pub fn no_input() {
     part("*");
     println!("{}", "no input".yellow());
@@ -193,35 +288,249 @@ pub fn submit_error() {
     println!("{}", "error".red());
 }
 
-fn display_time(time: u64) -> String {
-    let (div, unit) = match time {
-        _ if time < 1_000_000 => (1_000, "μs"),
-        _ if time < 1_000_000_000 => (1_000_000, "ms"),
-        _ => (1_000_000_000, "s"),
+#[derive(Clone, Copy)]
+pub enum TimeUnit {
+    Auto,
+    Nanos,
+    Micros,
+    Millis,
+    Secs,
+}
+
+#[derive(Clone, Copy)]
+pub struct TimeFormat {
+    pub fast: u64,
+    pub slow: u64,
+    pub precision: usize,
+    pub unit: TimeUnit,
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        Self {
+            fast: 20_000_000,
+            slow: 200_000_000,
+            precision: 3,
+            unit: TimeUnit::Auto,
+        }
+    }
+}
+
+const TIME_WIDTH: usize = 11;
+
+fn display_time(time: u64, format: &TimeFormat) -> String {
+    let unit = match format.unit {
+        TimeUnit::Auto if time < 1_000_000 => TimeUnit::Micros,
+        TimeUnit::Auto if time < 1_000_000_000 => TimeUnit::Millis,
+        TimeUnit::Auto => TimeUnit::Secs,
+        unit => unit,
+    };
+    let (div, unit) = match unit {
+        TimeUnit::Nanos => (1, "ns"),
+        TimeUnit::Micros => (1_000, glyph("μs", "us")),
+        TimeUnit::Millis => (1_000_000, "ms"),
+        TimeUnit::Auto | TimeUnit::Secs => (1_000_000_000, "s"),
     };
-    format!("{}{unit}", time as f64 / div as f64)
+    format!("{:.*}{unit}", format.precision, time as f64 / div as f64)
 }
 
-fn colored_time(time: u64) -> ColoredString {
-    let text = display_time(time);
+fn colored_time(time: u64, format: &TimeFormat) -> ColoredString {
+    color_time(display_time(time, format), time, format)
+}
+
+fn color_time(text: String, time: u64, format: &TimeFormat) -> ColoredString {
     match time {
-        _ if time < 20_000_000 => text.green(),
-        _ if time < 200_000_000 => text.yellow(),
+        _ if time < format.fast => text.green(),
+        _ if time < format.slow => text.yellow(),
         _ => text.red(),
     }
 }
 
-pub fn stats(total_time: u64, num_parts: u8) {
+const COLUMN_WIDTH: usize = 12;
+
+fn pad(text: &str, width: usize) -> String {
+    let len = text.chars().count();
+    format!("{text}{}", " ".repeat(width.saturating_sub(len)))
+}
+
+pub fn compare_header(label_width: usize, variants: &[&str]) {
+    print!("{}", " ".repeat(label_width + 2));
+    for variant in variants {
+        print!("{}", pad(variant, COLUMN_WIDTH).bold());
+    }
+    println!();
+}
+
+pub fn compare_row(
+    label: &str,
+    label_width: usize,
+    variants: &[&str],
+    results: &[RunResult],
+    expected: Option<&str>,
+    format: &TimeFormat,
+) -> bool {
+    print!(
+        "{}{}{}",
+        label,
+        ": ".dimmed(),
+        " ".repeat(label_width.saturating_sub(label.len()))
+    );
+    let mut answers = Vec::new();
+    for result in results {
+        let cell = match result {
+            RunResult::Success { answer, time } => {
+                if !answers.contains(&answer.as_str()) {
+                    answers.push(answer.as_str());
+                }
+                color_time(
+                    pad(&display_time(*time, format), COLUMN_WIDTH),
+                    *time,
+                    format,
+                )
+            }
+            RunResult::Panic { .. } => pad("panic", COLUMN_WIDTH).red(),
+            RunResult::Timeout => pad("timeout", COLUMN_WIDTH).red(),
+            RunResult::Unimplemented => pad("-", COLUMN_WIDTH).dimmed(),
+        };
+        print!("{cell}");
+    }
+    let failed = results
+        .iter()
+        .any(|result| matches!(result, RunResult::Panic { .. } | RunResult::Timeout));
+    match answers[..] {
+        [] => {
+            println!();
+            true
+        }
+        [answer] if !failed => {
+            compare_answer(answer, expected);
+            println!();
+            true
+        }
+        _ => {
+            println!("{}", "disagree".red());
+            for (variant, result) in variants.iter().zip(results) {
+                if let RunResult::Unimplemented = result {
+                    continue;
+                }
+                print!(
+                    "{}{}{}",
+                    " ".repeat(label_width + 2),
+                    variant,
+                    ": ".dimmed()
+                );
+                match result {
+                    RunResult::Success { answer, .. } => compare_answer(answer, expected),
+                    RunResult::Panic { .. } => print!("{}", "panic".red()),
+                    RunResult::Timeout => print!("{}", "timeout".red()),
+                    RunResult::Unimplemented => (),
+                }
+                println!();
+            }
+            false
+        }
+    }
+}
+
+fn compare_answer(got: &str, expected: Option<&str>) {
+    let got = Answer::new(got);
+    let text = match expected {
+        Some(expected) if Answer::new(expected).answer == got.answer => {
+            got.display().green().bold()
+        }
+        Some(_) => got.display().red().bold(),
+        None => got.display().yellow().bold(),
+    };
+    print!("{}{}{}", "[".dimmed(), text, "]".dimmed());
+}
+
+const INPUTS_COLUMN_WIDTH: usize = 15;
+
+pub fn inputs_table(parts: &[&str], statuses: &[InputStatus]) {
+    let label_width = statuses
+        .iter()
+        .map(|status| status.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+    print!("{}", pad("", label_width + 2));
+    print!("{}", pad("input", INPUTS_COLUMN_WIDTH).bold());
+    for part in parts {
+        print!(
+            "{}",
+            pad(&format!("answer {part}"), INPUTS_COLUMN_WIDTH).bold()
+        );
+        print!(
+            "{}",
+            pad(&format!("last {part}"), INPUTS_COLUMN_WIDTH).bold()
+        );
+    }
+    println!();
+    for status in statuses {
+        print!(
+            "{}{}{}",
+            status.name,
+            ": ".dimmed(),
+            " ".repeat(label_width.saturating_sub(status.name.chars().count()))
+        );
+        print!("{}", file_state(status.input, "present", "empty"));
+        for part in &status.parts {
+            print!("{}", file_state(part.answer, "known", "unknown"));
+            let last = match part.last {
+                Some(outcome) => {
+                    let text = pad(outcome.name(), INPUTS_COLUMN_WIDTH);
+                    match outcome {
+                        Outcome::Correct => text.green(),
+                        Outcome::Unknown | Outcome::Unimplemented => text.yellow(),
+                        _ => text.red(),
+                    }
+                }
+                None => pad("-", INPUTS_COLUMN_WIDTH).dimmed(),
+            };
+            print!("{last}");
+        }
+        println!();
+    }
+}
+
+fn file_state(state: FileState, present: &str, empty: &str) -> ColoredString {
+    match state {
+        FileState::Present => pad(present, INPUTS_COLUMN_WIDTH).green(),
+        FileState::Empty => pad(empty, INPUTS_COLUMN_WIDTH).yellow(),
+        FileState::Encrypted => pad("encrypted", INPUTS_COLUMN_WIDTH).cyan(),
+        FileState::Missing => pad("missing", INPUTS_COLUMN_WIDTH).red(),
+    }
+}
+
+pub fn stats(total_time: u64, num_parts: u8, profile: &str, format: &TimeFormat) {
+    log("profile".normal(), profile);
     log("parts".normal(), format!("{num_parts:02}"));
     if num_parts > 0 {
-        log("total".normal(), display_time(total_time));
+        log("total".normal(), display_time(total_time, format));
         log(
             "average".normal(),
-            display_time(total_time / num_parts as u64),
+            display_time(total_time / num_parts as u64, format),
         );
     }
 }
 
+pub fn summary(report: &Report) {
+    let counts: Vec<_> = Outcome::ALL
+        .into_iter()
+        .filter(|&outcome| report.count(outcome) > 0)
+        .map(|outcome| {
+            let text = format!("{} {}", report.count(outcome), outcome.name());
+            match outcome {
+                Outcome::Correct => text.green(),
+                Outcome::Unknown | Outcome::Unimplemented => text.yellow(),
+                _ => text.red(),
+            }
+            .to_string()
+        })
+        .collect();
+    log("summary".normal(), counts.join(&", ".dimmed().to_string()));
+}
+
 pub fn path(path: &Path) -> String {
     if path.is_absolute() {
         if let Ok(current_dir) = env::current_dir() {
@@ -261,21 +570,241 @@ impl<'a> Answer<'a> {
 }
 
 pub fn completion_header() {
-    println!("{}{}{}", " ".repeat(14), "1".repeat(10), "2".repeat(6));
-    println!("{}{}12345", " ".repeat(4 + 1), "1234567890".repeat(2),);
+    days_header(5);
+}
+
+fn days_header(offset: usize) {
+    println!(
+        "{}{}{}",
+        " ".repeat(offset + 9),
+        "1".repeat(10),
+        "2".repeat(6)
+    );
+    println!("{}{}12345", " ".repeat(offset), "1234567890".repeat(2));
+}
+
+fn completion_glyph(day: DayCompletion) -> ColoredString {
+    match day {
+        DayCompletion::None => " ".into(),
+        DayCompletion::Partial => glyph("★", "+").dimmed(),
+        DayCompletion::Full => glyph("★", "*").yellow(),
+    }
+}
+
+pub fn year_status(year: &str, days: &[Option<DayStatus>; 25]) {
+    const LABEL_WIDTH: usize = 9;
+    println!(
+        "{}{}{}",
+        " ".repeat(LABEL_WIDTH + 9),
+        "1".repeat(10),
+        "2".repeat(6)
+    );
+    println!("{}{}12345", pad(year, LABEL_WIDTH), "1234567890".repeat(2));
+    print!("{}", pad("  input", LABEL_WIDTH));
+    for day in days {
+        let glyph = match day.as_ref().map(|day| day.input) {
+            None => " ".normal(),
+            Some(FileState::Present) => glyph("■", "#").green(),
+            Some(FileState::Encrypted) => glyph("■", "%").cyan(),
+            Some(FileState::Empty | FileState::Missing) => glyph("□", "_").dimmed(),
+        };
+        print!("{glyph}");
+    }
+    println!();
+    print!("{}", pad("  answer", LABEL_WIDTH));
+    for day in days {
+        let glyph = match day {
+            None => " ".normal(),
+            Some(day) => {
+                let known = day
+                    .parts
+                    .iter()
+                    .filter(|part| matches!(part.answer, FileState::Present | FileState::Encrypted))
+                    .count();
+                match known {
+                    0 => glyph("○", "_").dimmed(),
+                    _ if known == day.parts.len() => glyph("●", "#").green(),
+                    _ => glyph("◐", "+").yellow(),
+                }
+            }
+        };
+        print!("{glyph}");
+    }
+    println!();
+    for part in 0..2 {
+        print!("{}", pad(&format!("  part {}", part + 1), LABEL_WIDTH));
+        let mut correct = 0;
+        for day in days {
+            let glyph = match day.as_ref().and_then(|day| day.parts.get(part)) {
+                None => " ".normal(),
+                Some(part) => match part.last {
+                    None => glyph("·", ".").dimmed(),
+                    Some(Outcome::Unimplemented) => "-".dimmed(),
+                    Some(Outcome::Correct) => {
+                        correct += 1;
+                        glyph("★", "*").yellow()
+                    }
+                    Some(Outcome::Incorrect) => glyph("✕", "x").red(),
+                    Some(_) => glyph("○", "?").yellow(),
+                },
+            };
+            print!("{glyph}");
+        }
+        println!(" {}", format!("{correct:02}").yellow());
+    }
+}
+
+pub fn year_completion_error(year: &str) {
+    println!("{year} {}", "error".red());
 }
 
 pub fn year_completion(year: &str, year_completion: YearCompletion) {
     print!("{year} ");
     for day in year_completion.days {
+        print!("{}", completion_glyph(day));
+    }
+    println!(" {}", format!("{:02}", year_completion.total).yellow());
+}
+
+pub fn calendar(year: &str, calendar: &Calendar) {
+    println!();
+    for line in &calendar.lines {
+        for text in line {
+            let styled = match &text.kind {
+                CalendarKind::Art(class) => match class.as_deref().and_then(art_color) {
+                    Some(color) => text.text.color(color),
+                    None => text.text.normal(),
+                },
+                CalendarKind::Locked => text.text.dimmed(),
+                CalendarKind::Day => text.text.normal(),
+                CalendarKind::Star(DayCompletion::Full) => text.text.bright_yellow().bold(),
+                CalendarKind::Star(_) => text.text.white(),
+                CalendarKind::Hidden => " ".repeat(text.text.chars().count()).normal(),
+            };
+            print!("{styled}");
+        }
+        println!();
+    }
+    println!();
+    println!("{year}: {} star(s)", format!("{}", calendar.stars).yellow());
+}
+
+fn art_color(class: &str) -> Option<Color> {
+    if let Some(color) = class.strip_prefix("calendar-color-") {
+        return match color.chars().next()? {
+            'g' => Some(Color::Green),
+            'y' => Some(Color::BrightYellow),
+            'o' | 'n' | 'u' => Some(Color::Yellow),
+            'r' => Some(Color::Red),
+            'b' => Some(Color::Blue),
+            'l' => Some(Color::BrightBlue),
+            'a' | 'c' => Some(Color::Cyan),
+            'p' | 'm' => Some(Color::Magenta),
+            'w' => Some(Color::BrightWhite),
+            's' | 'k' => Some(Color::BrightBlack),
+            _ => None,
+        };
+    }
+    const KEYWORDS: [(&str, Color); 8] = [
+        ("tree", Color::Green),
+        ("ornament", Color::BrightRed),
+        ("light", Color::BrightYellow),
+        ("star", Color::BrightYellow),
+        ("snow", Color::BrightWhite),
+        ("cloud", Color::BrightWhite),
+        ("water", Color::Blue),
+        ("sea", Color::Blue),
+    ];
+    KEYWORDS
+        .iter()
+        .find(|(keyword, _)| class.contains(keyword))
+        .map(|&(_, color)| color)
+}
+
+pub fn leaderboard_header() {
+    days_header(11);
+}
+
+pub fn leaderboard_row(rank: usize, score: u32, completion: YearCompletion, name: &str) {
+    print!(
+        "{}{} {} ",
+        format!("{rank:>3}").bold(),
+        ")".dimmed(),
+        pad_left(&score.to_string(), 5)
+    );
+    for day in completion.days {
+        print!("{}", completion_glyph(day));
+    }
+    println!(" {} {name}", format!("{:02}", completion.total).yellow());
+}
+
+pub fn leaderboard_day_header(year: &str, day: &str) {
+    println!(
+        "{}{}{}   {}{}{}",
+        year,
+        "/".dimmed(),
+        day,
+        pad_left("part 1", COLUMN_WIDTH).bold(),
+        pad_left("part 2", COLUMN_WIDTH).bold(),
+        pad_left("delta", COLUMN_WIDTH).bold()
+    );
+}
+
+pub fn leaderboard_day_row(rank: usize, times: [Option<i64>; 2], name: &str) {
+    print!("{}{}   ", format!("{rank:>6}").bold(), ")".dimmed());
+    for time in times {
+        match time {
+            Some(time) => print!("{}", pad_left(&clock(time), COLUMN_WIDTH).yellow()),
+            None => print!("{}", pad_left("-", COLUMN_WIDTH).dimmed()),
+        }
+    }
+    match times {
+        [Some(part_1), Some(part_2)] => {
+            print!("{}", pad_left(&clock(part_2 - part_1), COLUMN_WIDTH))
+        }
+        _ => print!("{}", pad_left("-", COLUMN_WIDTH).dimmed()),
+    }
+    println!("  {name}");
+}
+
+pub fn stats_header(year: &str) {
+    print!("{}", pad(year, 6).bold());
+    for part in ["part 1", "part 2"] {
         print!(
-            "{}",
-            match day {
-                DayCompletion::None => " ".into(),
-                DayCompletion::Partial => "★".dimmed(),
-                DayCompletion::Full => "★".yellow(),
-            }
+            "{}{}{}",
+            pad_left(&format!("{part} time"), COLUMN_WIDTH).bold(),
+            pad_left("rank", 8).bold(),
+            pad_left("score", 6).bold()
         );
     }
-    println!(" {}", format!("{:02}", year_completion.total).yellow());
+    println!();
+}
+
+pub fn stats_row(day: u8, parts: &[Option<PartStats>; 2]) {
+    print!("{}{}   ", format!("{day:02}").bold(), ":".dimmed());
+    for part in parts {
+        match part {
+            Some(part) => {
+                let time = part.time.map_or(">24h".into(), |time| clock(time as i64));
+                print!(
+                    "{}{}{}",
+                    pad_left(&time, COLUMN_WIDTH).yellow(),
+                    pad_left(&part.rank.to_string(), 8),
+                    pad_left(&part.score.to_string(), 6)
+                );
+            }
+            None => print!("{}", pad_left("-", COLUMN_WIDTH).dimmed()),
+        }
+    }
+    println!();
+}
+
+pub fn clock(secs: i64) -> String {
+    let secs = secs.max(0);
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn pad_left(text: &str, width: usize) -> String {
+    let len = text.chars().count();
+    format!("{}{text}", " ".repeat(width.saturating_sub(len)))
 }
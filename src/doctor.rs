@@ -0,0 +1,365 @@
+use std::{fs, path::Path, process};
+
+use regex::Regex;
+
+use crate::{
+    account, crypt, display,
+    error::{AocError, Context, Result, ToErr},
+    file::{self, DayDir, PathInfo},
+    run, ROOT,
+};
+
+struct Doctor {
+    fix: bool,
+    found: u32,
+    fixed: u32,
+    fixable: u32,
+}
+
+impl Doctor {
+    fn problem(&mut self, message: String, fixable: bool) -> bool {
+        display::problem(&message);
+        self.found += 1;
+        if fixable {
+            self.fixable += 1;
+        }
+        fixable && self.fix
+    }
+
+    fn fixed(&mut self, message: String) {
+        display::fixed(&message);
+        self.fixed += 1;
+    }
+}
+
+pub fn doctor(root: &Path, fix: bool) -> Result<()> {
+    let doctor = &mut Doctor {
+        fix,
+        found: 0,
+        fixed: 0,
+        fixable: 0,
+    };
+    check_root(doctor, root)?;
+    let days = file::day_dirs(root)?;
+    check_members(doctor, root, &days)?;
+    for day in &days {
+        check_day_crate(doctor, day)?;
+        check_day_data(doctor, day)?;
+    }
+    check_session(doctor, root)?;
+    check_program(doctor, "cargo");
+    check_program(doctor, "git");
+    let remaining = doctor.found - doctor.fixed;
+    if remaining == 0 {
+        if doctor.fixed == 0 {
+            display::success!("no problems found");
+        } else {
+            display::success!("fixed {} problem(s)", doctor.fixed);
+        }
+        return Ok(());
+    }
+    if !fix && doctor.fixable > 0 {
+        display::info!(
+            "{} problem(s) can be fixed with `aoc doctor --fix`",
+            doctor.fixable
+        );
+    }
+    AocError::Problems(remaining).err()
+}
+
+fn check_root(doctor: &mut Doctor, root: &Path) -> Result<()> {
+    if root.join(ROOT).try_is_file()? {
+        return Ok(());
+    }
+    doctor.problem(
+        format!("missing `{ROOT}` file, create it if this is an aocli workspace"),
+        false,
+    );
+    Ok(())
+}
+
+fn check_members(doctor: &mut Doctor, root: &Path, days: &[DayDir]) -> Result<()> {
+    let path = &root.join("Cargo.toml");
+    let contents = fs::read_to_string(path).context(AocError::WorkspaceCargo)?;
+    let mut toml: toml::Table = toml::from_str(&contents).context(AocError::WorkspaceCargo)?;
+    let members = toml
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("members"))
+        .and_then(|members| members.as_array_mut())
+        .ok_or(AocError::WorkspaceCargo)?;
+    let mut changed = false;
+    let listed: Vec<String> = members
+        .iter()
+        .filter_map(|member| member.as_str().map(String::from))
+        .collect();
+    let globs: Vec<_> = listed
+        .iter()
+        .filter(|member| is_glob(member))
+        .filter_map(|member| glob_regex(member))
+        .collect();
+    for member in &listed {
+        if is_glob(member) || root.join(member).join("Cargo.toml").try_is_file()? {
+            continue;
+        }
+        if doctor.problem(format!("workspace member `{member}` has no crate"), true) {
+            members.retain(|m| m.as_str() != Some(member));
+            changed = true;
+            doctor.fixed(format!("removed `{member}` from workspace members"));
+        }
+    }
+    for day in days {
+        let member = format!("{}/{}", day.year, day.day);
+        if listed.contains(&member) || globs.iter().any(|glob| glob.is_match(&member)) {
+            continue;
+        }
+        if !day.path.join("Cargo.toml").try_is_file()? {
+            if !day.path.join("src").try_is_dir()? {
+                continue;
+            }
+            doctor.problem(format!("{member} has no `Cargo.toml`"), false);
+            continue;
+        }
+        if doctor.problem(format!("{member} is not a workspace member"), true) {
+            members.push(member.clone().into());
+            changed = true;
+            doctor.fixed(format!("added {member} to workspace members"));
+        }
+    }
+    if changed {
+        fs::write(path, toml::to_string(&toml).unwrap()).context(AocError::FileWrite)?;
+    }
+    Ok(())
+}
+
+fn is_glob(member: &str) -> bool {
+    member.contains(['*', '?', '['])
+}
+
+fn glob_regex(pattern: &str) -> Option<Regex> {
+    let mut re = String::from("^");
+    let mut chars = pattern.trim_end_matches('/').chars().peekable();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        match c {
+            _ if in_class => {
+                in_class = c != ']';
+                re.push(c);
+            }
+            '*' if chars.next_if_eq(&'*').is_some() => re.push_str(".*"),
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '[' => {
+                in_class = true;
+                re.push('[');
+                if chars.next_if_eq(&'!').is_some() {
+                    re.push('^');
+                }
+            }
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).ok()
+}
+
+fn check_day_crate(doctor: &mut Doctor, day: &DayDir) -> Result<()> {
+    let DayDir { year, day, path } = day;
+    let cargo_path = &path.join("Cargo.toml");
+    if !cargo_path.try_is_file()? {
+        return Ok(());
+    }
+    let contents = fs::read_to_string(cargo_path).context(AocError::FileRead)?;
+    let Ok(mut toml) = contents.parse::<toml::Table>() else {
+        doctor.problem(format!("{year}/{day} has an invalid `Cargo.toml`"), false);
+        return Ok(());
+    };
+    let name = run::bin_name(year, day, None);
+    let main_path = format!("src/{day}.rs");
+    let mut changed = false;
+    let package_name = toml
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str());
+    if package_name != Some(&name)
+        && doctor.problem(format!("{year}/{day} crate is not named `{name}`"), true)
+    {
+        let package = toml
+            .entry("package")
+            .or_insert_with(|| toml::Table::new().into())
+            .as_table_mut()
+            .ok_or(AocError::DayCargo)?;
+        package.insert("name".into(), name.clone().into());
+        changed = true;
+        doctor.fixed(format!("renamed {year}/{day} crate to `{name}`"));
+    }
+    if !path.join(&main_path).try_is_file()? {
+        doctor.problem(format!("{year}/{day} has no `{main_path}`"), false);
+    }
+    let bins = toml
+        .entry("bin")
+        .or_insert_with(|| toml::Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or(AocError::DayCargo)?;
+    let variant_prefix = format!("{name}-");
+    let bin_name = |bin: &toml::Table| {
+        bin.get("name")
+            .and_then(|name| name.as_str())
+            .map(String::from)
+    };
+    let main_index = bins
+        .iter()
+        .position(|bin| bin.get("name").and_then(|name| name.as_str()) == Some(&name))
+        .or_else(|| {
+            bins.iter().position(|bin| {
+                bin.as_table()
+                    .and_then(bin_name)
+                    .is_some_and(|bin_name| !bin_name.starts_with(&variant_prefix))
+            })
+        });
+    match main_index.and_then(|i| bins[i].as_table_mut()) {
+        Some(bin) => {
+            let old_name = bin_name(bin).unwrap_or_default();
+            if old_name != name
+                && doctor.problem(
+                    format!("{year}/{day} binary `{old_name}` is not named `{name}`"),
+                    true,
+                )
+            {
+                bin.insert("name".into(), name.clone().into());
+                changed = true;
+                doctor.fixed(format!("renamed {year}/{day} binary to `{name}`"));
+            }
+            if bin.get("path").and_then(|path| path.as_str()) != Some(&main_path)
+                && doctor.problem(
+                    format!("{year}/{day} binary `{name}` does not point to `{main_path}`"),
+                    true,
+                )
+            {
+                bin.insert("path".into(), main_path.clone().into());
+                changed = true;
+                doctor.fixed(format!("set path of {year}/{day} binary to `{main_path}`"));
+            }
+        }
+        None => {
+            if doctor.problem(format!("{year}/{day} has no binary `{name}`"), true) {
+                let mut bin = toml::Table::new();
+                bin.insert("name".into(), name.clone().into());
+                bin.insert("path".into(), main_path.clone().into());
+                bins.insert(0, bin.into());
+                changed = true;
+                doctor.fixed(format!("added binary `{name}` to {year}/{day}"));
+            }
+        }
+    }
+    for bin in bins.iter().filter_map(|bin| bin.as_table()) {
+        let bin_name = bin.get("name").and_then(|name| name.as_str());
+        let bin_path = bin.get("path").and_then(|path| path.as_str());
+        if let (Some(bin_name), Some(bin_path)) = (bin_name, bin_path) {
+            if bin_name != name && !path.join(bin_path).try_is_file()? {
+                doctor.problem(
+                    format!("{year}/{day} binary `{bin_name}` points to missing `{bin_path}`"),
+                    false,
+                );
+            }
+        }
+    }
+    if changed {
+        fs::write(cargo_path, toml::to_string(&toml).unwrap()).context(AocError::FileWrite)?;
+    }
+    Ok(())
+}
+
+fn check_day_data(doctor: &mut Doctor, day: &DayDir) -> Result<()> {
+    let DayDir { year, day, path } = day;
+    let data_path = &path.join("data");
+    if !data_path.try_is_dir()? {
+        if doctor.problem(format!("{year}/{day} has no `data` directory"), true) {
+            fs::create_dir_all(data_path.join("actual")).context(AocError::FileWrite)?;
+            doctor.fixed(format!("created `data/actual` for {year}/{day}"));
+        } else {
+            return Ok(());
+        }
+    }
+    let parts: &[&str] = if day == "25" { &["1"] } else { &["1", "2"] };
+    let mut inputs = Vec::new();
+    for entry in data_path.read_dir().context(AocError::FileRead)? {
+        let entry = entry.context(AocError::FileRead)?;
+        if entry.path().try_is_dir()? {
+            inputs.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    inputs.sort();
+    for input in &inputs {
+        let input_path = &data_path.join(input);
+        if ["1", "2"].contains(&input.as_str()) {
+            doctor.problem(
+                format!("{year}/{day} has an input with reserved name `{input}`"),
+                false,
+            );
+            continue;
+        }
+        let mut files = vec![("input".to_string(), input_path.join("input"))];
+        for part in parts {
+            files.push((
+                format!("{part}/answer"),
+                input_path.join(part).join("answer"),
+            ));
+        }
+        for (name, file_path) in files {
+            if file_path.try_is_file()? || crypt::sealed_path(&file_path).try_is_file()? {
+                continue;
+            }
+            if doctor.problem(
+                format!("{year}/{day} input `{input}` has no `{name}` file"),
+                true,
+            ) {
+                if let Some(parent) = file_path.parent() {
+                    fs::create_dir_all(parent).context(AocError::FileWrite)?;
+                }
+                fs::write(&file_path, "").context(AocError::FileWrite)?;
+                doctor.fixed(format!("created `{name}` for {year}/{day} input `{input}`"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_session(doctor: &mut Doctor, root: &Path) -> Result<()> {
+    let path = &root.join(".session");
+    if !path.try_is_file()? {
+        if doctor.problem("missing `.session` file".into(), true) {
+            fs::write(path, "").context(AocError::FileWrite)?;
+            doctor.fixed("created empty `.session` file".into());
+        }
+        return Ok(());
+    }
+    let Some(contents) = path.read_file()?.get_contents() else {
+        display::info!("no session cookie in `.session`, network commands will not work");
+        return Ok(());
+    };
+    let cookie = contents.trim();
+    let cookie = cookie.strip_prefix("session=").unwrap_or(cookie);
+    if !account::is_valid_cookie(cookie) {
+        doctor.problem(
+            "`.session` does not contain a valid session cookie".into(),
+            false,
+        );
+    }
+    if account::is_world_readable(path)
+        && doctor.problem("`.session` is readable by other users".into(), true)
+    {
+        account::restrict(path)?;
+        doctor.fixed("restricted permissions of `.session`".into());
+    }
+    Ok(())
+}
+
+fn check_program(doctor: &mut Doctor, program: &str) {
+    let available = process::Command::new(program)
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !available {
+        doctor.problem(format!("`{program}` is not available"), false);
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use crate::display;
+use crate::{display, flags::Flag};
 
 #[derive(thiserror::Error, Debug)]
 pub enum AocError {
@@ -10,8 +10,8 @@ pub enum AocError {
     YearDir,
     #[error("invalid day directory")]
     DayDir,
-    #[error("must be an integer at least 2015")]
-    YearArg,
+    #[error("must be an event year from 2015 to {0}")]
+    YearArg(u16),
     #[error("must be an integer between 1 and 25")]
     DayArg,
     #[error("unexpected argument `{0}`")]
@@ -42,6 +42,18 @@ pub enum AocError {
     NoInput,
     #[error("network error")]
     Network,
+    #[error("failed to resolve `adventofcode.com` - check your internet connection")]
+    Dns,
+    #[error("secure connection to `adventofcode.com` failed")]
+    Tls,
+    #[error("request to `adventofcode.com` timed out")]
+    Timeout,
+    #[error("failed to connect through proxy")]
+    Proxy,
+    #[error("`adventofcode.com` is unavailable (HTTP {0}) - try again later")]
+    Outage(u16),
+    #[error("puzzle not unlocked yet - it unlocks in {0}")]
+    Locked(String),
     #[error("invalid session cookie")]
     Session,
     #[error("server response error")]
@@ -66,6 +78,68 @@ pub enum AocError {
     WorkspaceCargo,
     #[error("failed to add workspace member")]
     WorkspaceMember,
+    #[error("no executable found for binary `{0}`")]
+    MissingExecutable(String),
+    #[error("unknown flag `--{0}`")]
+    UnknownFlag(String),
+    #[error("missing value for flag `{0}`")]
+    MissingFlagValue(Flag),
+    #[error("flag `{0}` does not take a value")]
+    UnexpectedFlagValue(Flag),
+    #[error("flag `{0}` is not valid for this command")]
+    InvalidFlag(Flag),
+    #[error("failed to read `aoc.toml`")]
+    Config,
+    #[error("invalid variant name `{0}`")]
+    VariantName(String),
+    #[error("failed to add variant binary")]
+    VariantBin,
+    #[error("failed to read day Cargo.toml")]
+    DayCargo,
+    #[error("{0} run(s) failed")]
+    Failures(u32),
+    #[error("variants disagree on {0} answer(s)")]
+    Disagreements(u32),
+    #[error("invalid value for flag `{0}`: `{1}`")]
+    InvalidFlagValue(Flag, String),
+    #[error("{0} day(s) failed to download")]
+    GetFailures(u32),
+    #[error("invalid encryption key in `.key`")]
+    Key,
+    #[error("no encryption key found in `.key`")]
+    NoKey,
+    #[error("failed to encrypt file")]
+    Encrypt,
+    #[error("failed to decrypt file")]
+    Decrypt,
+    #[error("git command failed")]
+    Git,
+    #[error("no git repository found")]
+    NoGitRepository,
+    #[error("plaintext puzzle inputs or answers are staged for commit")]
+    PlaintextStaged,
+    #[error("{0} problem(s) found")]
+    Problems(u32),
+    #[error("input name `{0}` is reserved")]
+    ReservedInput(String),
+    #[error("input `{0}` not found")]
+    MissingInput(String),
+    #[error("failed to read clipboard")]
+    Clipboard,
+    #[error("failed to read leaderboard - check the ID and that your account can view it")]
+    Leaderboard,
+    #[error("invalid account name `{0}`")]
+    AccountName(String),
+    #[error("no session file for account `{0}` in `.sessions`")]
+    NoAccount(String),
+    #[error("failed to run `session_command`")]
+    SessionCommand,
+    #[error("session cookie must be a hexadecimal string")]
+    SessionFormat,
+    #[error("failed to read session cookie from standard input")]
+    SessionPrompt,
+    #[error("flags `{0}` and `{1}` cannot be used together")]
+    ConflictingFlags(Flag, Flag),
 }
 
 pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -73,6 +147,7 @@ pub type Result<T, E = Error> = core::result::Result<T, E>;
 pub struct Error {
     error: Vec<String>,
     usage: Vec<String>,
+    code: u8,
 }
 
 impl<E: ToString> From<E> for Error {
@@ -80,6 +155,7 @@ impl<E: ToString> From<E> for Error {
         Self {
             error: vec![value.to_string()],
             usage: Vec::new(),
+            code: 1,
         }
     }
 }
@@ -100,24 +176,27 @@ impl Error {
         self
     }
 
-    pub fn with_usage<U: ToString, F: Fn() -> U>(mut self, usage: F) -> Self {
-        self.usage.push(usage().to_string());
-        self
-    }
-
     pub fn usages<U: ToString>(mut self, usages: impl IntoIterator<Item = U>) -> Self {
         for usage in usages {
             self = self.usage(usage);
         }
         self
     }
+
+    pub fn code(mut self, code: u8) -> Self {
+        self.code = code;
+        self
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        self.code
+    }
 }
 
 pub trait Context<T, E: Into<Error>> {
     fn context<C: ToString>(self, context: C) -> Result<T, Error>;
     fn with_context<C: ToString, F: Fn() -> C>(self, context: F) -> Result<T, Error>;
     fn usage<U: ToString>(self, usage: U) -> Result<T, Error>;
-    fn with_usage<U: ToString, F: Fn() -> U>(self, usage: F) -> Result<T, Error>;
     fn usages<U: ToString>(self, usages: impl IntoIterator<Item = U>) -> Result<T, Error>;
 }
 
@@ -134,10 +213,6 @@ impl<T, E: Into<Error>> Context<T, E> for Result<T, E> {
         self.map_err(|e| e.into().usage(usage))
     }
 
-    fn with_usage<U: ToString, F: Fn() -> U>(self, usage: F) -> Result<T, Error> {
-        self.map_err(|e| e.into().with_usage(usage))
-    }
-
     fn usages<U: ToString>(self, usages: impl IntoIterator<Item = U>) -> Result<T, Error> {
         self.map_err(|e| e.into().usages(usages))
     }
@@ -204,6 +279,11 @@ pub enum Arg {
     Day,
     Part,
     Input,
+    NewYear,
+    NewDay,
+    NewInput,
+    Id,
+    Subcommand,
 }
 
 impl fmt::Display for Arg {
@@ -216,6 +296,11 @@ impl fmt::Display for Arg {
                 Self::Day => "DAY",
                 Self::Part => "PART",
                 Self::Input => "INPUT",
+                Self::NewYear => "NEW_YEAR",
+                Self::NewDay => "NEW_DAY",
+                Self::NewInput => "NEW_INPUT",
+                Self::Id => "ID",
+                Self::Subcommand => "SUBCOMMAND",
             }
         )
     }
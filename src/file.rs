@@ -1,5 +1,6 @@
 use std::{
     env, fs,
+    io::{self, Write},
     path::{Path, PathBuf},
 };
 
@@ -41,6 +42,40 @@ impl CurrentDirectory {
     }
 }
 
+pub struct DayDir {
+    pub year: String,
+    pub day: String,
+    pub path: PathBuf,
+}
+
+pub fn day_dirs(root: &Path) -> Result<Vec<DayDir>> {
+    let mut days = Vec::new();
+    for year_dir in root.read_dir().context(AocError::FileRead)? {
+        let year_path = year_dir.context(AocError::FileRead)?.path();
+        let Some(year) = year_from_dir(&year_path) else {
+            continue;
+        };
+        if !year_path.try_is_dir()? {
+            continue;
+        }
+        for day_dir in year_path.read_dir().context(AocError::FileRead)? {
+            let path = day_dir.context(AocError::FileRead)?.path();
+            let Some(day) = day_from_dir(&path) else {
+                continue;
+            };
+            if path.try_is_dir()? {
+                days.push(DayDir {
+                    year: year.clone(),
+                    day,
+                    path,
+                });
+            }
+        }
+    }
+    days.sort_by(|a, b| (&a.year, &a.day).cmp(&(&b.year, &b.day)));
+    Ok(days)
+}
+
 fn dir_name(path: &Path) -> Option<String> {
     Some(path.file_name()?.to_str()?.to_string())
 }
@@ -57,6 +92,24 @@ fn day_from_dir(path: &Path) -> Option<String> {
     ((1..=25).contains(&num) && day.len() == 2).then_some(day)
 }
 
+#[cfg(unix)]
+pub fn write_private(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents.as_ref())
+}
+
+#[cfg(not(unix))]
+pub fn write_private(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    fs::write(path, contents)
+}
+
 pub trait PathInfo
 where
     Self: AsRef<Path>,
@@ -79,17 +132,12 @@ where
         })
     }
 
-    fn read_file(&self) -> Result<FileInfo> {
+    fn read_file(&self) -> Result<FileInfo<'_>> {
         Ok(FileInfo {
             path: self.as_ref(),
             contents: if self.try_is_file()? {
                 let contents = fs::read_to_string(self).context(AocError::FileRead)?;
-                let contents = contents.trim_end();
-                if contents.is_empty() {
-                    FileContents::Empty
-                } else {
-                    FileContents::Contents(contents.to_string())
-                }
+                FileContents::of(&contents)
             } else {
                 FileContents::Nonexistent
             },
@@ -109,7 +157,7 @@ where
     }
 }
 
-impl<P: AsRef<Path>> PathInfo for P {}
+impl<P: AsRef<Path> + ?Sized> PathInfo for P {}
 
 pub struct FileInfo<'a> {
     path: &'a Path,
@@ -122,7 +170,25 @@ pub enum FileContents {
     Nonexistent,
 }
 
+impl FileContents {
+    fn of(contents: &str) -> Self {
+        let contents = contents.trim_end();
+        if contents.is_empty() {
+            Self::Empty
+        } else {
+            Self::Contents(contents.to_string())
+        }
+    }
+}
+
 impl<'a> FileInfo<'a> {
+    pub fn from_contents(path: &'a Path, contents: &str) -> Self {
+        Self {
+            path,
+            contents: FileContents::of(contents),
+        }
+    }
+
     pub fn try_contents(self) -> Result<String> {
         match self.contents {
             FileContents::Empty => Err(AocError::EmptyFile(display::path(self.path)).into()),
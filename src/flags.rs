@@ -0,0 +1,185 @@
+use std::fmt;
+
+use crate::error::{AocError, Result, ToErr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    Profile,
+    Features,
+    Rustflags,
+    Variant,
+    Timeout,
+    Junit,
+    Accept,
+    AcceptAll,
+    Install,
+    Fix,
+    KeepData,
+    FromStdin,
+    FromFile,
+    FromClipboard,
+    Answer1,
+    Answer2,
+    Day,
+    Account,
+    New,
+    Refresh,
+    Color,
+    Ascii,
+}
+
+impl Flag {
+    const ALL: &'static [Self] = &[
+        Self::Profile,
+        Self::Features,
+        Self::Rustflags,
+        Self::Variant,
+        Self::Timeout,
+        Self::Junit,
+        Self::Accept,
+        Self::AcceptAll,
+        Self::Install,
+        Self::Fix,
+        Self::KeepData,
+        Self::FromStdin,
+        Self::FromFile,
+        Self::FromClipboard,
+        Self::Answer1,
+        Self::Answer2,
+        Self::Day,
+        Self::Account,
+        Self::New,
+        Self::Refresh,
+        Self::Color,
+        Self::Ascii,
+    ];
+    const GLOBAL: &'static [Self] = &[Self::Color, Self::Ascii];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Profile => "profile",
+            Self::Features => "features",
+            Self::Rustflags => "rustflags",
+            Self::Variant => "variant",
+            Self::Timeout => "timeout",
+            Self::Junit => "junit",
+            Self::Accept => "accept",
+            Self::AcceptAll => "accept-all",
+            Self::Install => "install",
+            Self::Fix => "fix",
+            Self::KeepData => "keep-data",
+            Self::FromStdin => "from-stdin",
+            Self::FromFile => "from-file",
+            Self::FromClipboard => "from-clipboard",
+            Self::Answer1 => "answer1",
+            Self::Answer2 => "answer2",
+            Self::Day => "day",
+            Self::Account => "account",
+            Self::New => "new",
+            Self::Refresh => "refresh",
+            Self::Color => "color",
+            Self::Ascii => "ascii",
+        }
+    }
+
+    fn takes_value(self) -> bool {
+        match self {
+            Self::Profile
+            | Self::Features
+            | Self::Rustflags
+            | Self::Variant
+            | Self::Timeout
+            | Self::Junit
+            | Self::FromFile
+            | Self::Answer1
+            | Self::Answer2
+            | Self::Day
+            | Self::Account
+            | Self::Color => true,
+            Self::Accept
+            | Self::AcceptAll
+            | Self::Install
+            | Self::Fix
+            | Self::KeepData
+            | Self::FromStdin
+            | Self::FromClipboard
+            | Self::New
+            | Self::Refresh
+            | Self::Ascii => false,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|flag| flag.name() == name)
+    }
+}
+
+impl fmt::Display for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "--{}", self.name())
+    }
+}
+
+#[derive(Default)]
+pub struct Flags {
+    flags: Vec<(Flag, Option<String>)>,
+}
+
+impl Flags {
+    pub fn parse<'a>(args: &[&'a str]) -> Result<(Vec<&'a str>, Self)> {
+        let mut positional = Vec::with_capacity(args.len());
+        let mut flags = Vec::new();
+        let mut args = args.iter();
+        while let Some(&arg) = args.next() {
+            let Some(arg) = arg.strip_prefix("--") else {
+                positional.push(arg);
+                continue;
+            };
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg, None),
+            };
+            let flag = Flag::from_name(name).ok_or_else(|| AocError::UnknownFlag(name.into()))?;
+            let value = match (flag.takes_value(), value) {
+                (true, Some(value)) => Some(value),
+                (true, None) => match args.next() {
+                    Some(value) => Some(value.to_string()),
+                    None => return AocError::MissingFlagValue(flag).err(),
+                },
+                (false, Some(_)) => return AocError::UnexpectedFlagValue(flag).err(),
+                (false, None) => None,
+            };
+            flags.push((flag, value));
+        }
+        Ok((positional, Self { flags }))
+    }
+
+    pub fn assert_allowed(&self, allowed: &[Flag]) -> Result<()> {
+        for (flag, _) in &self.flags {
+            if !allowed.contains(flag) && !Flag::GLOBAL.contains(flag) {
+                return AocError::InvalidFlag(*flag).err();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn has(&self, flag: Flag) -> bool {
+        self.flags.iter().any(|(f, _)| *f == flag)
+    }
+
+    pub fn value(&self, flag: Flag) -> Option<&str> {
+        self.flags
+            .iter()
+            .rev()
+            .find(|(f, _)| *f == flag)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    pub fn values(&self, flag: Flag) -> Vec<&str> {
+        self.flags
+            .iter()
+            .filter(|(f, _)| *f == flag)
+            .filter_map(|(_, value)| value.as_deref())
+            .collect()
+    }
+}
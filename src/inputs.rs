@@ -0,0 +1,225 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    crypt, display,
+    error::{AocError, Context, Result, ToErr},
+    file::{FileInfo, PathInfo},
+    report::Outcome,
+};
+
+#[derive(Clone, Copy)]
+pub enum FileState {
+    Present,
+    Empty,
+    Encrypted,
+    Missing,
+}
+
+impl FileState {
+    fn of(path: &Path) -> Result<Self> {
+        let file = path.read_file()?;
+        Ok(if file.has_contents() {
+            Self::Present
+        } else if crypt::sealed_path(path).try_is_file()? {
+            Self::Encrypted
+        } else if path.try_is_file()? {
+            Self::Empty
+        } else {
+            Self::Missing
+        })
+    }
+}
+
+pub struct PartStatus {
+    pub answer: FileState,
+    pub last: Option<Outcome>,
+}
+
+pub struct InputStatus {
+    pub name: String,
+    pub input: FileState,
+    pub parts: Vec<PartStatus>,
+}
+
+pub struct DayStatus {
+    pub input: FileState,
+    pub parts: Vec<PartStatus>,
+}
+
+pub fn day_status(path: &Path, day: &str, input: &str) -> Result<DayStatus> {
+    let parts: &[&str] = if day == "25" { &["1"] } else { &["1", "2"] };
+    let input_path = &path.join("data").join(input);
+    let mut part_statuses = Vec::new();
+    for part in parts {
+        let part_path = &input_path.join(part);
+        part_statuses.push(PartStatus {
+            answer: FileState::of(&part_path.join("answer"))?,
+            last: last_outcome(path, part_path)?,
+        });
+    }
+    Ok(DayStatus {
+        input: FileState::of(&input_path.join("input"))?,
+        parts: part_statuses,
+    })
+}
+
+pub fn list_year(path: &Path, year: &str) -> Result<()> {
+    let mut empty = true;
+    for day in 1..=25 {
+        let day = &format!("{day:02}");
+        let path = &path.join(day);
+        if path.try_is_dir()? {
+            if !empty {
+                println!();
+            }
+            list_day(path, year, day)?;
+            empty = false;
+        }
+    }
+    if empty {
+        display::info!("no day directories found");
+    }
+    Ok(())
+}
+
+pub fn list_day(path: &Path, year: &str, day: &str) -> Result<()> {
+    let parts: &[&str] = if day == "25" { &["1"] } else { &["1", "2"] };
+    let mut statuses = Vec::new();
+    for name in input_names(path)? {
+        let input_path = &path.join("data").join(&name);
+        let mut part_statuses = Vec::new();
+        for part in parts {
+            let part_path = &input_path.join(part);
+            part_statuses.push(PartStatus {
+                answer: FileState::of(&part_path.join("answer"))?,
+                last: last_outcome(path, part_path)?,
+            });
+        }
+        statuses.push(InputStatus {
+            name,
+            input: FileState::of(&input_path.join("input"))?,
+            parts: part_statuses,
+        });
+    }
+    display::day(year, day);
+    println!();
+    if statuses.is_empty() {
+        display::info!("no inputs found");
+        return Ok(());
+    }
+    display::inputs_table(parts, &statuses);
+    Ok(())
+}
+
+pub fn input_names(path: &Path) -> Result<Vec<String>> {
+    let data_path = &path.join("data");
+    let mut names = Vec::new();
+    if !data_path.try_is_dir()? {
+        return Ok(names);
+    }
+    for entry in data_path.read_dir().context(AocError::FileRead)? {
+        let entry = entry.context(AocError::FileRead)?;
+        if entry.path().try_is_dir()? {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    names.sort_by_key(|name| (name != "actual", name.clone()));
+    Ok(names)
+}
+
+fn last_outcome(path: &Path, part_path: &Path) -> Result<Option<Outcome>> {
+    let out_path = &part_path.join("out");
+    if out_path.join("unimplemented").try_is_file()? {
+        return Ok(Some(Outcome::Unimplemented));
+    }
+    let Some(answer) = out_path.join("answer").read_file()?.get_contents() else {
+        return Ok(None);
+    };
+    let expected = crypt::read(path, &part_path.join("answer"))
+        .ok()
+        .and_then(FileInfo::get_contents);
+    Ok(Some(match expected {
+        Some(expected) if expected == answer => Outcome::Correct,
+        Some(_) => Outcome::Incorrect,
+        None => Outcome::Unknown,
+    }))
+}
+
+pub fn rename(path: &Path, input: &str, new_input: &str) -> Result<()> {
+    assert_not_reserved(input)?;
+    let input_path = &existing_input(path, input)?;
+    let new_path = &new_input_path(path, new_input)?;
+    fs::rename(input_path, new_path).context(AocError::FileWrite)?;
+    display::success!("renamed input `{input}` to `{new_input}`");
+    Ok(())
+}
+
+pub fn copy(path: &Path, input: &str, new_day_path: &Path, new_input: &str) -> Result<()> {
+    assert_not_reserved(input)?;
+    let input_path = &existing_input(path, input)?;
+    new_day_path.assert_day_dir()?;
+    let new_path = &new_input_path(new_day_path, new_input)?;
+    copy_dir(input_path, new_path).context(AocError::FileWrite)?;
+    display::success!("copied input `{input}` to {}", display::path(new_path));
+    Ok(())
+}
+
+pub fn remove(path: &Path, input: &str) -> Result<()> {
+    assert_not_reserved(input)?;
+    let input_path = &existing_input(path, input)?;
+    if !display::confirm(&format!("remove input `{input}` and its answers?")) {
+        display::info!("cancelled");
+        return Ok(());
+    }
+    fs::remove_dir_all(input_path).context(AocError::FileWrite)?;
+    display::success!("removed input `{input}`");
+    Ok(())
+}
+
+fn assert_not_reserved(input: &str) -> Result<()> {
+    if ["1", "2"].contains(&input) || crypt::is_secret_input(input) {
+        return AocError::ReservedInput(input.into()).err();
+    }
+    Ok(())
+}
+
+fn existing_input(path: &Path, input: &str) -> Result<PathBuf> {
+    let input_path = path.join("data").join(input);
+    if ["1", "2"].contains(&input) || !input_path.try_is_dir()? {
+        return AocError::MissingInput(input.into()).err();
+    }
+    Ok(input_path)
+}
+
+fn new_input_path(path: &Path, input: &str) -> Result<PathBuf> {
+    assert_not_reserved(input)?;
+    let valid = !input.is_empty() && input != "." && input != ".." && !input.contains(['/', '\\']);
+    if !valid {
+        return AocError::InputNameFormat.err().context(AocError::InputName);
+    }
+    let input_path = path.join("data").join(input);
+    if input_path.try_exists().context(AocError::FileRead)? {
+        return AocError::PathExists(display::path(&input_path)).err();
+    }
+    Ok(input_path)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in from.read_dir()? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_name() == "out" || path.extension().is_some_and(|ext| ext == "enc") {
+            continue;
+        }
+        if path.try_is_dir()? {
+            copy_dir(&path, &to.join(entry.file_name()))?;
+        } else {
+            fs::copy(&path, to.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
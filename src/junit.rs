@@ -0,0 +1,129 @@
+use std::{fmt::Write, fs, path::Path};
+
+use crate::{
+    error::Result,
+    report::{Case, Outcome, Report},
+};
+
+pub fn write(path: &Path, report: &Report) -> Result<()> {
+    let mut suites: Vec<(String, Vec<&Case>)> = Vec::new();
+    for case in report.cases() {
+        let name = format!("{}/{}", case.year, case.day);
+        match suites.iter_mut().find(|(suite, _)| *suite == name) {
+            Some((_, cases)) => cases.push(case),
+            None => suites.push((name, vec![case])),
+        }
+    }
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let cases: Vec<_> = report.cases().iter().collect();
+    writeln!(xml, "<testsuites name=\"aocli\"{}>", attributes(&cases))?;
+    for (name, cases) in &suites {
+        writeln!(
+            xml,
+            "  <testsuite name=\"{}\"{}>",
+            escape(name),
+            attributes(cases)
+        )?;
+        for case in cases {
+            write_case(&mut xml, case)?;
+        }
+        writeln!(xml, "  </testsuite>")?;
+    }
+    writeln!(xml, "</testsuites>")?;
+    fs::write(path, xml)?;
+    Ok(())
+}
+
+fn attributes(cases: &[&Case]) -> String {
+    let count = |outcomes: &[Outcome]| {
+        cases
+            .iter()
+            .filter(|case| outcomes.contains(&case.outcome))
+            .count()
+    };
+    let time: u64 = cases.iter().filter_map(|case| case.time).sum();
+    format!(
+        " tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\"",
+        cases.len(),
+        count(&[Outcome::Incorrect]),
+        count(&[Outcome::Panic, Outcome::Timeout, Outcome::BuildError]),
+        count(&[Outcome::Unknown, Outcome::Unimplemented]),
+        seconds(time)
+    )
+}
+
+fn write_case(xml: &mut String, case: &Case) -> Result<()> {
+    let classname = format!("y{}d{}", case.year, case.day);
+    let name = match (&case.part, &case.input) {
+        (Some(part), Some(input)) => format!("part {part} ({input})"),
+        _ => "build".into(),
+    };
+    write!(
+        xml,
+        "    <testcase classname=\"{}\" name=\"{}\" time=\"{}\"",
+        escape(&classname),
+        escape(&name),
+        seconds(case.time.unwrap_or(0))
+    )?;
+    match case.outcome {
+        Outcome::Correct => {
+            writeln!(xml, "/>")?;
+            return Ok(());
+        }
+        Outcome::Incorrect => {
+            writeln!(
+                xml,
+                ">\n      <failure type=\"incorrect\" message=\"incorrect answer\">{}</failure>",
+                escape(&format!(
+                    "expected: {}\nactual: {}",
+                    case.expected.as_deref().unwrap_or_default(),
+                    case.answer.as_deref().unwrap_or_default()
+                ))
+            )?;
+        }
+        Outcome::Panic => {
+            writeln!(
+                xml,
+                ">\n      <error type=\"panic\" message=\"panic\">{}</error>",
+                escape(case.output.as_deref().unwrap_or_default())
+            )?;
+        }
+        Outcome::Timeout | Outcome::BuildError => {
+            let name = case.outcome.name();
+            writeln!(xml, ">\n      <error type=\"{name}\" message=\"{name}\"/>")?;
+        }
+        Outcome::Unknown => {
+            writeln!(
+                xml,
+                ">\n      <skipped message=\"unknown answer: {}\"/>",
+                escape(case.answer.as_deref().unwrap_or_default())
+            )?;
+        }
+        Outcome::Unimplemented => {
+            writeln!(xml, ">\n      <skipped message=\"unimplemented\"/>")?;
+        }
+    }
+    writeln!(xml, "    </testcase>")?;
+    Ok(())
+}
+
+fn seconds(time: u64) -> String {
+    format!("{:.9}", time as f64 / 1_000_000_000.0)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' | '\t' | '\r' => escaped.push(c),
+            c if c.is_control() => (),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,33 +1,58 @@
+mod account;
 mod action;
+mod config;
+mod crypt;
+mod date;
 mod days;
 mod display;
+mod doctor;
 mod error;
 mod file;
+mod flags;
+mod inputs;
+mod junit;
 mod network;
+mod report;
 mod run;
 
-use std::env;
+use std::{collections::HashMap, env, path::Path, process::ExitCode, time::Duration};
 
+use account::Account;
+use action::{Accept, InputSource};
+use config::{BuildConfig, Config, TimingConfig};
+use display::{TimeFormat, TimeUnit};
 use error::{AocError, Arg, Context, ErrorDisplayer, Result, ToErr};
 use file::{CurrentDirectory, PathInfo};
+use flags::{Flag, Flags};
+use run::{BuildOptions, RunOptions, Timing};
 
 pub const ROOT: &str = "aoc-root";
 
-fn main() {
-    cli().display_err();
+fn main() -> ExitCode {
+    let args: Vec<_> = env::args().collect();
+    let args: Vec<_> = args.iter().map(|arg| arg.trim()).collect();
+    style(&args);
+    match cli(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            let code = e.exit_code();
+            e.display_err();
+            ExitCode::from(code)
+        }
+    }
 }
 
-fn cli() -> Result<()> {
+fn cli(args: &[&str]) -> Result<()> {
     use Command::*;
     use CurrentDirectory::*;
 
-    let args: Vec<_> = env::args().collect();
-    let args: Vec<_> = args.iter().map(|arg| arg.trim()).collect();
     if args.len() <= 1 {
         return "must provide a command".err();
     }
     let command = Command::from_arg(args[1])?;
-    let args = &args[2..];
+    let (args, flags) = &Flags::parse(&args[2..])?;
+    flags.assert_allowed(command.flags())?;
+    assert_color(flags)?;
     let (root, current) = &CurrentDirectory::get()?;
     match (command, current) {
         (Init, _) => {
@@ -41,15 +66,31 @@ fn cli() -> Result<()> {
             assert_args(args, &[Arg::Year, Arg::Day]).usage(USAGE)?;
             let year = &year_from_arg(args[0]).usage(USAGE)?;
             let day = &day_from_arg(args[1]).usage(USAGE)?;
-            action::new_day(&root.join(year).join(day), year, day)
+            let path = &root.join(year).join(day);
+            if let Some(variant) = flags.value(Flag::Variant) {
+                path.assert_day_dir()?;
+                return action::new_variant(path, year, day, variant);
+            }
+            action::new_day(path, year, day, &new_options(root, flags)?)
         }
         (New, Year { year }) => {
             const USAGE: &str = "new <DAY>";
             assert_args(args, &[Arg::Day]).usage(USAGE)?;
             let day = &day_from_arg(args[0]).usage(USAGE)?;
-            action::new_day(&root.join(year).join(day), year, day)
+            let path = &root.join(year).join(day);
+            if let Some(variant) = flags.value(Flag::Variant) {
+                path.assert_day_dir()?;
+                return action::new_variant(path, year, day, variant);
+            }
+            action::new_day(path, year, day, &new_options(root, flags)?)
+        }
+        (New, Day { year, day }) => {
+            let Some(variant) = flags.value(Flag::Variant) else {
+                return Err(AocError::CommandDir("new".into()).into());
+            };
+            assert_args(args, &[]).usage("new --variant <VARIANT>")?;
+            action::new_variant(&root.join(year).join(day), year, day, variant)
         }
-        (New, Day { .. }) => Err(AocError::CommandDir("new".into()).into()),
         (Add, Root) => {
             const USAGE: &str = "add <YEAR> <DAY> <INPUT>";
             assert_args(args, &[Arg::Year, Arg::Day, Arg::Input]).usage(USAGE)?;
@@ -63,7 +104,7 @@ fn cli() -> Result<()> {
                     .err()
                     .usage(USAGE);
             }
-            action::add_input(path, input)
+            action::add_input(path, input, &input_source(flags)?, answers(flags))
         }
         (Add, Year { year }) => {
             const USAGE: &str = "add <DAY> <INPUT>";
@@ -77,7 +118,7 @@ fn cli() -> Result<()> {
                     .err()
                     .usage(USAGE);
             }
-            action::add_input(path, input)
+            action::add_input(path, input, &input_source(flags)?, answers(flags))
         }
         (Add, Day { year, day }) => {
             const USAGE: &str = "add <INPUT>";
@@ -88,29 +129,32 @@ fn cli() -> Result<()> {
                     .err()
                     .usage(USAGE);
             }
-            action::add_input(&root.join(year).join(day), input)
+            action::add_input(
+                &root.join(year).join(day),
+                input,
+                &input_source(flags)?,
+                answers(flags),
+            )
         }
         (Get, Root) => {
-            const USAGE: &str = "get <YEAR> <DAY>";
-            assert_args(args, &[Arg::Year, Arg::Day]).usage(USAGE)?;
-            let year = &year_from_arg(args[0]).usage(USAGE)?;
-            let day = &day_from_arg(args[1]).usage(USAGE)?;
-            let path = &root.join(year).join(day);
-            path.assert_day_dir()?;
-            action::get(path, year, day)
+            const USAGE_1: &str = "get <YEAR> [--new] [--refresh]";
+            const USAGE_2: &str = "get <YEAR> <DAY> [--new] [--refresh]";
+            const USAGE_3: &str = "get <YEAR> days <DAYS> [--new] [--refresh]";
+            const USAGES: &[&str] = &[USAGE_1, USAGE_2, USAGE_3];
+            assert_first_args(args, &[Arg::Year]).usages(USAGES)?;
+            let year = &year_from_arg(args[0]).usages(USAGES)?;
+            get_subcommand(root, year, &args[1..], flags, USAGES)
         }
         (Get, Year { year }) => {
-            const USAGE: &str = "get <DAY>";
-            assert_args(args, &[Arg::Day]).usage(USAGE)?;
-            let day = &day_from_arg(args[0]).usage(USAGE)?;
-            let path = &root.join(year).join(day);
-            path.assert_day_dir()?;
-            action::get(path, year, day)
+            const USAGE_1: &str = "get [--new] [--refresh]";
+            const USAGE_2: &str = "get <DAY> [--new] [--refresh]";
+            const USAGE_3: &str = "get days <DAYS> [--new] [--refresh]";
+            get_subcommand(root, year, args, flags, &[USAGE_1, USAGE_2, USAGE_3])
         }
         (Get, Day { year, day }) => {
-            assert_args(args, &[]).usage("get")?;
+            assert_args(args, &[]).usage("get [--refresh]")?;
             let path = &root.join(year).join(day);
-            action::get(path, year, day)
+            action::get(path, year, day, &account(flags)?, flags.has(Flag::Refresh))
         }
         (Clean, Root) => {
             const USAGE_1: &str = "clean <YEAR>";
@@ -160,19 +204,26 @@ fn cli() -> Result<()> {
             if args.len() == 1 {
                 let path = &root.join(year);
                 path.assert_year_dir()?;
-                return action::run_days(path, year, 1..=25);
+                return action::run_days(path, year, 1..=25, &run_options(root, flags, false)?);
             }
             if args[1] == "days" || args[1] == "d" {
                 let path = &root.join(year);
                 path.assert_year_dir()?;
                 let days = days::parse_days(&args[2..]).usages(USAGES)?;
-                return action::run_days(path, year, days);
+                return action::run_days(path, year, days, &run_options(root, flags, false)?);
             }
             let day = &day_from_arg(args[1]).usages(USAGES)?;
             let path = &root.join(year).join(day);
             path.assert_day_dir()?;
             let (input, parts) = input_parts(&args[2..]).usages(USAGES)?;
-            action::run_day(path, year, day, input, parts, false)
+            action::run_day(
+                path,
+                year,
+                day,
+                input,
+                parts,
+                &run_options(root, flags, false)?,
+            )
         }
         (Run, Year { year }) => {
             const USAGE_1: &str = "run";
@@ -180,23 +231,42 @@ fn cli() -> Result<()> {
             const USAGE_3: &str = "run days <DAYS>";
             const USAGES: &[&str] = &[USAGE_1, USAGE_2, USAGE_3];
             if args.is_empty() {
-                return action::run_days(&root.join(year), year, 1..=25);
+                return action::run_days(
+                    &root.join(year),
+                    year,
+                    1..=25,
+                    &run_options(root, flags, false)?,
+                );
             }
             if args[0] == "days" || args[0] == "d" {
                 let path = &root.join(year);
                 let days = days::parse_days(&args[1..]).usages(USAGES)?;
-                return action::run_days(path, year, days);
+                return action::run_days(path, year, days, &run_options(root, flags, false)?);
             }
             let day = &day_from_arg(args[0]).usages(USAGES)?;
             let path = &root.join(year).join(day);
             path.assert_day_dir()?;
             let (input, parts) = input_parts(&args[1..]).usages(USAGES)?;
-            action::run_day(path, year, day, input, parts, false)
+            action::run_day(
+                path,
+                year,
+                day,
+                input,
+                parts,
+                &run_options(root, flags, false)?,
+            )
         }
         (Run, Day { year, day }) => {
             let (input, parts) = input_parts(args).usage("run [INPUT] [PART]")?;
             let path = &root.join(year).join(day);
-            action::run_day(path, year, day, input, parts, false)
+            action::run_day(
+                path,
+                year,
+                day,
+                input,
+                parts,
+                &run_options(root, flags, false)?,
+            )
         }
         (Debug, Root) => {
             const USAGE: &str = "debug <YEAR> <DAY> [INPUT] [PART]";
@@ -206,7 +276,14 @@ fn cli() -> Result<()> {
             let path = &root.join(year).join(day);
             path.assert_day_dir()?;
             let (input, parts) = input_parts(&args[2..]).usage(USAGE)?;
-            action::run_day(path, year, day, input, parts, true)
+            action::run_day(
+                path,
+                year,
+                day,
+                input,
+                parts,
+                &run_options(root, flags, true)?,
+            )
         }
         (Debug, Year { year }) => {
             const USAGE: &str = "debug <DAY> [INPUT] [PART]";
@@ -215,12 +292,26 @@ fn cli() -> Result<()> {
             let path = &root.join(year).join(day);
             path.assert_day_dir()?;
             let (input, parts) = input_parts(&args[1..]).usage(USAGE)?;
-            action::run_day(path, year, day, input, parts, true)
+            action::run_day(
+                path,
+                year,
+                day,
+                input,
+                parts,
+                &run_options(root, flags, true)?,
+            )
         }
         (Debug, Day { year, day }) => {
             let (input, parts) = input_parts(args).usage("debug [INPUT] [PART]")?;
             let path = &root.join(year).join(day);
-            action::run_day(path, year, day, input, parts, true)
+            action::run_day(
+                path,
+                year,
+                day,
+                input,
+                parts,
+                &run_options(root, flags, true)?,
+            )
         }
         (Test, Root) => {
             const USAGE_1: &str = "test <YEAR>";
@@ -234,19 +325,41 @@ fn cli() -> Result<()> {
             if args.len() == 1 {
                 let path = &root.join(year);
                 path.assert_year_dir()?;
-                return action::test_days(path, year, 1..=25);
+                return action::test_days(
+                    path,
+                    year,
+                    1..=25,
+                    accept(flags),
+                    flags.value(Flag::Junit).map(Path::new),
+                    &run_options(root, flags, false)?,
+                );
             }
             if args[1] == "days" || args[1] == "d" {
                 let path = &root.join(year);
                 path.assert_year_dir()?;
                 let days = days::parse_days(&args[2..]).usages(USAGES)?;
-                return action::test_days(path, year, days);
+                return action::test_days(
+                    path,
+                    year,
+                    days,
+                    accept(flags),
+                    flags.value(Flag::Junit).map(Path::new),
+                    &run_options(root, flags, false)?,
+                );
             }
             let day = &day_from_arg(args[1]).usages(USAGES)?;
             let path = &root.join(year).join(day);
             path.assert_day_dir()?;
             let parts = Parts::from_args(&args[2..]).usages(USAGES)?;
-            action::test_day(path, year, day, parts)
+            action::test_day(
+                path,
+                year,
+                day,
+                parts,
+                accept(flags),
+                flags.value(Flag::Junit).map(Path::new),
+                &run_options(root, flags, false)?,
+            )
         }
         (Test, Year { year }) => {
             const USAGE_1: &str = "test";
@@ -254,23 +367,77 @@ fn cli() -> Result<()> {
             const USAGE_3: &str = "test days <DAYS>";
             const USAGES: &[&str] = &[USAGE_1, USAGE_2, USAGE_3];
             if args.is_empty() {
-                return action::test_days(&root.join(year), year, 1..=25);
+                return action::test_days(
+                    &root.join(year),
+                    year,
+                    1..=25,
+                    accept(flags),
+                    flags.value(Flag::Junit).map(Path::new),
+                    &run_options(root, flags, false)?,
+                );
             }
             if args[0] == "days" || args[0] == "d" {
                 let path = &root.join(year);
                 let days = days::parse_days(&args[1..]).usages(USAGES)?;
-                return action::test_days(path, year, days);
+                return action::test_days(
+                    path,
+                    year,
+                    days,
+                    accept(flags),
+                    flags.value(Flag::Junit).map(Path::new),
+                    &run_options(root, flags, false)?,
+                );
             }
             let day = &day_from_arg(args[0]).usages(USAGES)?;
             let path = &root.join(year).join(day);
             path.assert_day_dir()?;
             let parts = Parts::from_args(&args[1..]).usages(USAGES)?;
-            action::test_day(path, year, day, parts)
+            action::test_day(
+                path,
+                year,
+                day,
+                parts,
+                accept(flags),
+                flags.value(Flag::Junit).map(Path::new),
+                &run_options(root, flags, false)?,
+            )
         }
         (Test, Day { year, day }) => {
             let parts = Parts::from_args(args).usage("test [PART]")?;
             let path = &root.join(year).join(day);
-            action::test_day(path, year, day, parts)
+            action::test_day(
+                path,
+                year,
+                day,
+                parts,
+                accept(flags),
+                flags.value(Flag::Junit).map(Path::new),
+                &run_options(root, flags, false)?,
+            )
+        }
+        (Compare, Root) => {
+            const USAGE: &str = "compare <YEAR> <DAY> [PART]";
+            assert_first_args(args, &[Arg::Year, Arg::Day]).usage(USAGE)?;
+            let year = &year_from_arg(args[0]).usage(USAGE)?;
+            let day = &day_from_arg(args[1]).usage(USAGE)?;
+            let path = &root.join(year).join(day);
+            path.assert_day_dir()?;
+            let parts = Parts::from_args(&args[2..]).usage(USAGE)?;
+            action::compare(path, year, day, parts, &run_options(root, flags, false)?)
+        }
+        (Compare, Year { year }) => {
+            const USAGE: &str = "compare <DAY> [PART]";
+            assert_first_args(args, &[Arg::Day]).usage(USAGE)?;
+            let day = &day_from_arg(args[0]).usage(USAGE)?;
+            let path = &root.join(year).join(day);
+            path.assert_day_dir()?;
+            let parts = Parts::from_args(&args[1..]).usage(USAGE)?;
+            action::compare(path, year, day, parts, &run_options(root, flags, false)?)
+        }
+        (Compare, Day { year, day }) => {
+            let parts = Parts::from_args(args).usage("compare [PART]")?;
+            let path = &root.join(year).join(day);
+            action::compare(path, year, day, parts, &run_options(root, flags, false)?)
         }
         (Submit, Root) => {
             const USAGE: &str = "submit <YEAR> <DAY> [ANSWER]";
@@ -280,7 +447,7 @@ fn cli() -> Result<()> {
             let path = &root.join(year).join(day);
             path.assert_day_dir()?;
             let answer = answer_from_args(&args[2..]).usage(USAGE)?;
-            action::submit(path, year, day, answer)
+            action::submit(path, year, day, answer, &account(flags)?)
         }
         (Submit, Year { year }) => {
             const USAGE: &str = "submit <DAY> [ANSWER]";
@@ -289,12 +456,12 @@ fn cli() -> Result<()> {
             let path = &root.join(year).join(day);
             path.assert_day_dir()?;
             let answer = answer_from_args(&args[1..]).usage(USAGE)?;
-            action::submit(path, year, day, answer)
+            action::submit(path, year, day, answer, &account(flags)?)
         }
         (Submit, Day { year, day }) => {
             let answer = answer_from_args(args).usage("submit [ANSWER]")?;
             let path = &root.join(year).join(day);
-            action::submit(path, year, day, answer)
+            action::submit(path, year, day, answer, &account(flags)?)
         }
         (Open, Root | Unknown) => {
             const USAGE_1: &str = "open <YEAR>";
@@ -332,16 +499,16 @@ fn cli() -> Result<()> {
             const USAGE_3: &str = "progress <YEAR> <DAY>";
             const USAGES: &[&str] = &[USAGE_1, USAGE_2, USAGE_3];
             match args.len() {
-                0 => action::all_progress(root),
+                0 => action::all_progress(root, &account(flags)?),
                 1 => {
                     let year = &year_from_arg(args[0]).usages(USAGES)?;
-                    action::year_progress(root, year)
+                    action::year_progress(root, year, &account(flags)?)
                 }
                 _ => {
                     assert_args(args, &[Arg::Year, Arg::Day]).usages(USAGES)?;
                     let year = &year_from_arg(args[0]).usages(USAGES)?;
                     let day = &day_from_arg(args[1]).usages(USAGES)?;
-                    action::day_progress(root, year, day)
+                    action::day_progress(root, year, day, &account(flags)?)
                 }
             }
         }
@@ -350,16 +517,157 @@ fn cli() -> Result<()> {
             const USAGE_2: &str = "progress <DAY>";
             const USAGES: &[&str] = &[USAGE_1, USAGE_2];
             if args.is_empty() {
-                action::year_progress(root, year)
+                action::year_progress(root, year, &account(flags)?)
             } else {
                 assert_args(args, &[Arg::Day]).usages(USAGES)?;
                 let day = &day_from_arg(args[0]).usages(USAGES)?;
-                action::day_progress(root, year, day)
+                action::day_progress(root, year, day, &account(flags)?)
             }
         }
         (Progress, Day { year, day }) => {
             assert_args(args, &[]).usage("progress")?;
-            action::day_progress(root, year, day)
+            action::day_progress(root, year, day, &account(flags)?)
+        }
+        (Inputs, Root) => {
+            const USAGE_1: &str = "inputs <YEAR>";
+            const USAGE_2: &str = "inputs <YEAR> <DAY> [SUBCOMMAND]";
+            const USAGES: &[&str] = &[USAGE_1, USAGE_2];
+            assert_first_args(args, &[Arg::Year]).usages(USAGES)?;
+            let year = &year_from_arg(args[0]).usages(USAGES)?;
+            if args.len() == 1 {
+                let path = &root.join(year);
+                path.assert_year_dir()?;
+                return inputs::list_year(path, year);
+            }
+            let day = &day_from_arg(args[1]).usages(USAGES)?;
+            inputs_subcommand(root, year, day, &args[2..], "inputs <YEAR> <DAY>")
+        }
+        (Inputs, Year { year }) => {
+            if args.is_empty() {
+                return inputs::list_year(&root.join(year), year);
+            }
+            const USAGE_1: &str = "inputs";
+            const USAGE_2: &str = "inputs <DAY> [SUBCOMMAND]";
+            let day = &day_from_arg(args[0]).usages([USAGE_1, USAGE_2])?;
+            inputs_subcommand(root, year, day, &args[1..], "inputs <DAY>")
+        }
+        (Inputs, Day { year, day }) => inputs_subcommand(root, year, day, args, "inputs"),
+        (Remove, Root) => {
+            const USAGE: &str = "remove <YEAR> <DAY>";
+            assert_args(args, &[Arg::Year, Arg::Day]).usage(USAGE)?;
+            let year = &year_from_arg(args[0]).usage(USAGE)?;
+            let day = &day_from_arg(args[1]).usage(USAGE)?;
+            root.join(year).join(day).assert_day_dir()?;
+            action::remove_day(root, year, day, flags.has(Flag::KeepData))
+        }
+        (Remove, Year { year }) => {
+            const USAGE: &str = "remove <DAY>";
+            assert_args(args, &[Arg::Day]).usage(USAGE)?;
+            let day = &day_from_arg(args[0]).usage(USAGE)?;
+            root.join(year).join(day).assert_day_dir()?;
+            action::remove_day(root, year, day, flags.has(Flag::KeepData))
+        }
+        (Remove, Day { .. }) => Err(AocError::CommandDir("remove".into()).into()),
+        (Move, Root) => {
+            const USAGE: &str = "mv <YEAR> <DAY> <NEW_YEAR> <NEW_DAY>";
+            assert_args(args, &[Arg::Year, Arg::Day, Arg::NewYear, Arg::NewDay]).usage(USAGE)?;
+            let year = &year_from_arg(args[0]).usage(USAGE)?;
+            let day = &day_from_arg(args[1]).usage(USAGE)?;
+            let new_year = &year_from_arg(args[2]).usage(USAGE)?;
+            let new_day = &day_from_arg(args[3]).usage(USAGE)?;
+            root.join(year).join(day).assert_day_dir()?;
+            action::move_day(root, year, day, new_year, new_day)
+        }
+        (Move, Year { year }) => {
+            const USAGE: &str = "mv <DAY> <NEW_DAY>";
+            assert_args(args, &[Arg::Day, Arg::NewDay]).usage(USAGE)?;
+            let day = &day_from_arg(args[0]).usage(USAGE)?;
+            let new_day = &day_from_arg(args[1]).usage(USAGE)?;
+            root.join(year).join(day).assert_day_dir()?;
+            action::move_day(root, year, day, year, new_day)
+        }
+        (Move, Day { .. }) => Err(AocError::CommandDir("mv".into()).into()),
+        (Leaderboard, Root | Year { .. } | Day { .. }) => {
+            const USAGE: &str = "leaderboard <ID> [YEAR] [--day <DAY>]";
+            assert_first_args(args, &[Arg::Id]).usage(USAGE)?;
+            let id = args[0];
+            if id.parse::<u64>().is_err() {
+                return AocError::InvalidArg(Arg::Id, id.into()).err().usage(USAGE);
+            }
+            let year = &default_year(&args[1..], current).usage(USAGE)?;
+            let day = flags
+                .value(Flag::Day)
+                .map(day_from_arg)
+                .transpose()
+                .usage(USAGE)?;
+            action::leaderboard(root, id, year, day.as_deref(), &account(flags)?)
+        }
+        (Calendar, Root | Year { .. } | Day { .. }) => {
+            const USAGE: &str = "calendar [YEAR]";
+            let year = &default_year(args, current).usage(USAGE)?;
+            action::calendar(root, year, &account(flags)?)
+        }
+        (Stats, Root | Year { .. } | Day { .. }) => {
+            const USAGE: &str = "stats [YEAR]";
+            let year = &default_year(args, current).usage(USAGE)?;
+            action::stats(root, year, &account(flags)?)
+        }
+        (Status, Root) => {
+            const USAGE: &str = "status [YEAR]";
+            match args[..] {
+                [] => action::status(root, None, &account(flags)?),
+                [year] => {
+                    let year = &year_from_arg(year).usage(USAGE)?;
+                    root.join(year).assert_year_dir()?;
+                    action::status(root, Some(year), &account(flags)?)
+                }
+                _ => AocError::ExtraArg(args[1].into()).err().usage(USAGE),
+            }
+        }
+        (Status, Year { year } | Day { year, .. }) => {
+            assert_args(args, &[]).usage("status")?;
+            action::status(root, Some(year), &account(flags)?)
+        }
+        (Session, Root | Year { .. } | Day { .. }) => {
+            const USAGES: &[&str] = &["session check", "session set"];
+            match args[..] {
+                ["check"] => action::session_check(root, &account(flags)?),
+                ["set"] => action::session_set(root, &account(flags)?),
+                [] => AocError::MissingArg(Arg::Subcommand).err().usages(USAGES),
+                ["check" | "set", extra, ..] | [extra, ..] => {
+                    AocError::ExtraArg(extra.into()).err().usages(USAGES)
+                }
+            }
+        }
+        (Lock, Root | Year { .. } | Day { .. }) => {
+            assert_args(args, &[]).usage("lock")?;
+            action::lock(root)
+        }
+        (Unlock, Root | Year { .. } | Day { .. }) => {
+            assert_args(args, &[]).usage("unlock")?;
+            action::unlock(root)
+        }
+        (Check, Root | Year { .. } | Day { .. }) => {
+            assert_args(args, &[]).usage("check [--install]")?;
+            if flags.has(Flag::Install) {
+                action::install_hook(root)
+            } else {
+                action::check(root)
+            }
+        }
+        (Doctor, Unknown) => {
+            assert_args(args, &[]).usage("doctor [--fix]")?;
+            if !root.join("Cargo.toml").try_is_file()? {
+                return format!("unknown directory - failed to find file `{ROOT}`").err();
+            }
+            if flags.has(Flag::Fix) {
+                return format!("refusing to fix a directory without file `{ROOT}`").err();
+            }
+            doctor::doctor(root, false)
+        }
+        (Doctor, _) => {
+            assert_args(args, &[]).usage("doctor [--fix]")?;
+            doctor::doctor(root, flags.has(Flag::Fix))
         }
         (Help, _) => {
             assert_args(args, &[]).usage("help")?;
@@ -369,6 +677,266 @@ fn cli() -> Result<()> {
     }
 }
 
+fn new_options(root: &Path, flags: &Flags) -> Result<BuildOptions> {
+    Ok(build_options(Config::load(root)?.build, flags, false))
+}
+
+fn run_options(root: &Path, flags: &Flags, debug: bool) -> Result<RunOptions> {
+    let config = Config::load(root)?;
+    let timeout = match flags.value(Flag::Timeout) {
+        Some(value) => Some(
+            value
+                .parse::<f64>()
+                .map_err(|_| AocError::InvalidFlagValue(Flag::Timeout, value.into()))?,
+        ),
+        None => config.run.timeout,
+    };
+    let timeout = timeout
+        .map(Duration::try_from_secs_f64)
+        .transpose()
+        .context("timeout must be a positive number of seconds")?;
+    Ok(RunOptions {
+        build: build_options(config.build, flags, debug),
+        variant: flags.value(Flag::Variant).map(String::from),
+        timeout,
+        actual: account(flags)?.input(),
+        timing: timing(config.timing)?,
+    })
+}
+
+fn timing(config: TimingConfig) -> Result<Timing> {
+    let default = TimeFormat::default();
+    let fast = threshold(config.fast, default.fast)?;
+    let slow = threshold(config.slow, default.slow)?;
+    let unit = match config.unit.as_deref() {
+        None | Some("auto") => TimeUnit::Auto,
+        Some("ns") => TimeUnit::Nanos,
+        Some("us" | "μs") => TimeUnit::Micros,
+        Some("ms") => TimeUnit::Millis,
+        Some("s") => TimeUnit::Secs,
+        Some(unit) => {
+            return format!("unknown time unit `{unit}`, expected auto, ns, us, ms or s")
+                .err()
+                .context(AocError::Config);
+        }
+    };
+    let format = TimeFormat {
+        fast,
+        slow,
+        precision: config.precision.unwrap_or(default.precision),
+        unit,
+    };
+    let mut days = HashMap::new();
+    for (key, day_config) in config.days {
+        let Some((year, day)) = key.split_once('/') else {
+            return format!("invalid day `{key}`, expected <YEAR>/<DAY>")
+                .err()
+                .context(AocError::Config);
+        };
+        let year = year_from_arg(year).context(AocError::Config)?;
+        let day = day_from_arg(day).context(AocError::Config)?;
+        let fast = threshold(day_config.fast, fast)?;
+        let slow = threshold(day_config.slow, slow)?;
+        days.insert((year, day), (fast, slow));
+    }
+    Ok(Timing { format, days })
+}
+
+fn threshold(millis: Option<f64>, default: u64) -> Result<u64> {
+    let Some(millis) = millis else {
+        return Ok(default);
+    };
+    Duration::try_from_secs_f64(millis / 1000.0)
+        .map(|duration| duration.as_nanos() as u64)
+        .context("timing thresholds must be positive numbers of milliseconds")
+        .context(AocError::Config)
+}
+
+fn build_options(config: BuildConfig, flags: &Flags, debug: bool) -> BuildOptions {
+    let profile = if debug {
+        "dev"
+    } else {
+        flags
+            .value(Flag::Profile)
+            .or(config.profile.as_deref())
+            .unwrap_or("release")
+    };
+    let mut features = Vec::new();
+    for value in flags.values(Flag::Features) {
+        features.extend(
+            value
+                .split([',', ' '])
+                .filter(|feature| !feature.is_empty())
+                .map(String::from),
+        );
+    }
+    let rustflags = flags.value(Flag::Rustflags).map(String::from);
+    BuildOptions {
+        profile: profile.into(),
+        features,
+        config_features: config.features,
+        rustflags: rustflags.or(config.rustflags),
+    }
+}
+
+fn input_source(flags: &Flags) -> Result<InputSource> {
+    let sources: Vec<_> = [Flag::FromStdin, Flag::FromFile, Flag::FromClipboard]
+        .into_iter()
+        .filter(|&flag| flags.has(flag))
+        .collect();
+    if let [first, second, ..] = sources[..] {
+        return AocError::ConflictingFlags(first, second).err();
+    }
+    Ok(match sources.first() {
+        Some(Flag::FromStdin) => InputSource::Stdin,
+        Some(Flag::FromFile) => InputSource::File(flags.value(Flag::FromFile).unwrap().into()),
+        Some(_) => InputSource::Clipboard,
+        None => InputSource::Empty,
+    })
+}
+
+fn answers(flags: &Flags) -> [Option<&str>; 2] {
+    [flags.value(Flag::Answer1), flags.value(Flag::Answer2)].map(|answer| answer.map(str::trim))
+}
+
+fn account(flags: &Flags) -> Result<Account> {
+    Account::new(flags.value(Flag::Account))
+}
+
+fn style(args: &[&str]) {
+    let mut color = None;
+    let mut ascii = false;
+    let mut args = args.iter().skip(2);
+    while let Some(&arg) = args.next() {
+        match arg.split_once('=') {
+            Some(("--color", value)) => color = Some(value),
+            None if arg == "--color" => color = args.next().copied(),
+            None if arg == "--ascii" => ascii = true,
+            _ => (),
+        }
+    }
+    let color = match color {
+        Some("always") => Some(true),
+        Some("never") => Some(false),
+        _ => None,
+    };
+    display::set_style(color, ascii);
+}
+
+fn assert_color(flags: &Flags) -> Result<()> {
+    match flags.value(Flag::Color) {
+        None | Some("auto" | "always" | "never") => Ok(()),
+        Some(value) => "must be `auto`, `always` or `never`"
+            .err()
+            .context(AocError::InvalidFlagValue(Flag::Color, value.into())),
+    }
+}
+
+fn accept(flags: &Flags) -> Accept {
+    if flags.has(Flag::AcceptAll) {
+        Accept::All
+    } else if flags.has(Flag::Accept) {
+        Accept::Ask
+    } else {
+        Accept::Never
+    }
+}
+
+fn get_subcommand(
+    root: &Path,
+    year: &str,
+    args: &[&str],
+    flags: &Flags,
+    usages: &[&str],
+) -> Result<()> {
+    let refresh = flags.has(Flag::Refresh);
+    let new = flags
+        .has(Flag::New)
+        .then(|| new_options(root, flags))
+        .transpose()?;
+    match args {
+        [] => action::get_days(root, year, None, &account(flags)?, refresh, new.as_ref()),
+        ["days" | "d", days @ ..] => {
+            let days = days::parse_days(days).usages(usages)?;
+            action::get_days(
+                root,
+                year,
+                Some(days),
+                &account(flags)?,
+                refresh,
+                new.as_ref(),
+            )
+        }
+        [day] => {
+            let day = &day_from_arg(day).usages(usages)?;
+            let path = &root.join(year).join(day);
+            match &new {
+                Some(options) if !path.join("Cargo.toml").try_is_file()? => {
+                    action::new_day(path, year, day, options)?
+                }
+                _ => path.assert_day_dir()?,
+            }
+            action::get(path, year, day, &account(flags)?, refresh)
+        }
+        _ => AocError::ExtraArg(args[1].into()).err().usages(usages),
+    }
+}
+
+fn inputs_subcommand(
+    root: &Path,
+    year: &str,
+    day: &str,
+    args: &[&str],
+    prefix: &str,
+) -> Result<()> {
+    let path = &root.join(year).join(day);
+    path.assert_day_dir()?;
+    let usages = [
+        prefix.to_string(),
+        format!("{prefix} rename <INPUT> <NEW_INPUT>"),
+        format!("{prefix} cp <INPUT> [[YEAR/]DAY/]<NEW_INPUT>"),
+        format!("{prefix} rm <INPUT>"),
+    ];
+    let Some(&subcommand) = args.first() else {
+        return inputs::list_day(path, year, day);
+    };
+    let args = &args[1..];
+    match subcommand {
+        "rename" | "mv" => {
+            assert_args(args, &[Arg::Input, Arg::NewInput]).usage(&usages[1])?;
+            inputs::rename(path, args[0], args[1])
+        }
+        "cp" | "copy" => {
+            assert_args(args, &[Arg::Input, Arg::NewInput]).usage(&usages[2])?;
+            let (new_year, new_day, new_input) = match args[1].split('/').collect::<Vec<_>>()[..] {
+                [input] => (year.to_string(), day.to_string(), input),
+                [new_day, input] => (
+                    year.to_string(),
+                    day_from_arg(new_day).usage(&usages[2])?,
+                    input,
+                ),
+                [new_year, new_day, input] => (
+                    year_from_arg(new_year).usage(&usages[2])?,
+                    day_from_arg(new_day).usage(&usages[2])?,
+                    input,
+                ),
+                _ => {
+                    return AocError::InvalidArg(Arg::NewInput, args[1].into())
+                        .err()
+                        .usage(&usages[2]);
+                }
+            };
+            let new_path = &root.join(new_year).join(new_day);
+            inputs::copy(path, args[0], new_path, new_input)
+        }
+        "rm" | "remove" => {
+            assert_args(args, &[Arg::Input]).usage(&usages[3])?;
+            inputs::remove(path, args[0])
+        }
+        _ => AocError::ExtraArg(subcommand.into()).err().usages(usages),
+    }
+}
+
 fn assert_args(args: &[&str], params: &[Arg]) -> Result<()> {
     match args.len().cmp(&params.len()) {
         std::cmp::Ordering::Less => AocError::MissingArg(params[args.len()]).err(),
@@ -442,50 +1010,150 @@ impl Parts {
 
 enum Command {
     Add,
+    Calendar,
+    Check,
     Clean,
+    Compare,
     Debug,
+    Doctor,
     Get,
     Help,
     Init,
+    Inputs,
+    Leaderboard,
+    Lock,
+    Move,
     New,
     Open,
     Progress,
+    Remove,
     Run,
+    Session,
+    Stats,
+    Status,
     Submit,
     Test,
+    Unlock,
 }
 
 impl Command {
+    fn flags(&self) -> &'static [Flag] {
+        match self {
+            Self::New => &[
+                Flag::Profile,
+                Flag::Features,
+                Flag::Rustflags,
+                Flag::Variant,
+            ],
+            Self::Run => &[
+                Flag::Profile,
+                Flag::Features,
+                Flag::Rustflags,
+                Flag::Variant,
+                Flag::Timeout,
+                Flag::Account,
+            ],
+            Self::Test => &[
+                Flag::Profile,
+                Flag::Features,
+                Flag::Rustflags,
+                Flag::Variant,
+                Flag::Timeout,
+                Flag::Accept,
+                Flag::AcceptAll,
+                Flag::Junit,
+            ],
+            Self::Compare => &[
+                Flag::Profile,
+                Flag::Features,
+                Flag::Rustflags,
+                Flag::Timeout,
+            ],
+            Self::Debug => &[
+                Flag::Features,
+                Flag::Rustflags,
+                Flag::Variant,
+                Flag::Timeout,
+                Flag::Account,
+            ],
+            Self::Add => &[
+                Flag::FromStdin,
+                Flag::FromFile,
+                Flag::FromClipboard,
+                Flag::Answer1,
+                Flag::Answer2,
+            ],
+            Self::Check => &[Flag::Install],
+            Self::Doctor => &[Flag::Fix],
+            Self::Get => &[Flag::Account, Flag::New, Flag::Refresh],
+            Self::Calendar
+            | Self::Submit
+            | Self::Progress
+            | Self::Session
+            | Self::Stats
+            | Self::Status => &[Flag::Account],
+            Self::Leaderboard => &[Flag::Day, Flag::Account],
+            Self::Remove => &[Flag::KeepData],
+            _ => &[],
+        }
+    }
+
     fn from_arg(arg: &str) -> Result<Self> {
         match arg {
             "add" | "a" => Ok(Self::Add),
+            "calendar" => Ok(Self::Calendar),
+            "check" => Ok(Self::Check),
             "clean" => Ok(Self::Clean),
+            "compare" | "c" => Ok(Self::Compare),
             "debug" | "d" => Ok(Self::Debug),
+            "doctor" => Ok(Self::Doctor),
             "get" | "g" => Ok(Self::Get),
             "help" => Ok(Self::Help),
             "init" => Ok(Self::Init),
+            "inputs" | "i" => Ok(Self::Inputs),
+            "leaderboard" | "l" => Ok(Self::Leaderboard),
+            "lock" => Ok(Self::Lock),
+            "mv" | "move" => Ok(Self::Move),
             "new" | "n" => Ok(Self::New),
             "open" | "o" => Ok(Self::Open),
             "progress" | "p" => Ok(Self::Progress),
+            "remove" | "rm" => Ok(Self::Remove),
             "run" | "r" => Ok(Self::Run),
+            "session" => Ok(Self::Session),
+            "stats" => Ok(Self::Stats),
+            "status" => Ok(Self::Status),
             "submit" | "s" => Ok(Self::Submit),
             "test" | "t" => Ok(Self::Test),
+            "unlock" => Ok(Self::Unlock),
             _ => format!("invalid command `{arg}`").err(),
         }
     }
 }
 
+fn default_year(args: &[&str], current: &CurrentDirectory) -> Result<String> {
+    match (args, current) {
+        ([], CurrentDirectory::Year { year } | CurrentDirectory::Day { year, .. }) => {
+            Ok(year.clone())
+        }
+        ([], _) => Ok(date::latest_event_year().to_string()),
+        ([year], _) => year_from_arg(year),
+        _ => AocError::ExtraArg(args[1].into()).err(),
+    }
+}
+
 fn year_from_arg(arg: &str) -> Result<String> {
+    let latest = date::latest_event_year();
     let mut num = arg
         .parse::<u16>()
-        .map_err(|_| AocError::YearArg)
+        .map_err(|_| AocError::YearArg(latest))
         .context(AocError::InvalidArg(Arg::Year, arg.into()))?;
     if num < 1000 {
         num += 2000;
     }
-    (num >= 2015)
+    (2015..=latest)
+        .contains(&num)
         .then(|| num.to_string())
-        .ok_or(AocError::YearArg)
+        .ok_or(AocError::YearArg(latest))
         .context(AocError::InvalidArg(Arg::Year, arg.into()))
 }
 
@@ -1,172 +1,709 @@
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
 use regex::Regex;
+use ring::digest;
+use serde::Deserialize;
+
+use crate::{
+    config::Config,
+    date, display,
+    error::{AocError, Context, Error, Result, ToErr},
+};
+
+const USER_AGENT: &str = concat!(
+    "aocli/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/sncxyz/aocli)"
+);
+const HOST: &str = "adventofcode.com";
+const REQUEST_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_RETRIES: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+const DAY_TTL: Duration = Duration::from_secs(5 * 60);
+const YEAR_TTL: Duration = Duration::from_secs(15 * 60);
+const LEADERBOARD_TTL: Duration = Duration::from_secs(15 * 60);
+
+pub struct Client {
+    agent: ureq::Agent,
+    session: String,
+    dir: PathBuf,
+    retries: u32,
+}
+
+enum Page {
+    Ok(String),
+    Status(u16, String),
+}
+
+impl Client {
+    pub fn new(root: &Path, session: String) -> Result<Self> {
+        let config = Config::load(root)?.network;
+        let timeout = config
+            .timeout
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .context("network timeout must be a positive number of seconds")?
+            .unwrap_or(DEFAULT_TIMEOUT);
+        let mut agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(timeout);
+        if let Some(proxy) = proxy()? {
+            agent = agent.proxy(proxy);
+        }
+        Ok(Self {
+            agent: agent.build(),
+            session,
+            dir: root.join("target").join("aoc"),
+            retries: config.retries.unwrap_or(DEFAULT_RETRIES),
+        })
+    }
+
+    fn get(&self, url: &str, ttl: Option<Duration>) -> Result<Page> {
+        if let Some(text) = ttl.and_then(|ttl| self.cached(url, ttl)) {
+            return Ok(Page::Ok(text));
+        }
+        match self.fetch(url) {
+            Ok(Page::Ok(text)) => {
+                self.store(url, &text);
+                Ok(Page::Ok(text))
+            }
+            Ok(page) => Ok(page),
+            Err(e) if ttl.is_some() => Ok(Page::Ok(self.stale(url).ok_or(e)?)),
+            Err(e) => Err(e),
+        }
+    }
 
-use crate::error::{AocError, Context, Result, ToErr};
+    fn fetch(&self, url: &str) -> Result<Page> {
+        let mut attempt = 0;
+        loop {
+            self.wait_for_turn();
+            let result = self.agent.get(url).set("cookie", &self.session).call();
+            let transient = match &result {
+                Ok(_) => false,
+                Err(ureq::Error::Status(code, _)) => *code >= 500,
+                Err(ureq::Error::Transport(transport)) => is_transient(transport),
+            };
+            if !transient || attempt >= self.retries {
+                return page(result);
+            }
+            let delay = RETRY_DELAY
+                .saturating_mul(2_u32.saturating_pow(attempt))
+                .min(MAX_RETRY_DELAY);
+            display::info!("request failed, retrying in {}s", delay.as_secs());
+            thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    fn post(&self, url: &str, params: &[(&str, &str)]) -> Result<Page> {
+        self.wait_for_turn();
+        page(
+            self.agent
+                .post(url)
+                .set("cookie", &self.session)
+                .send_form(params),
+        )
+    }
 
-pub fn get_input(year: &str, day: &str, session: &str) -> Result<String> {
-    let day = &day.parse::<u8>().unwrap().to_string();
-    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-    let response = ureq::get(&url).set("cookie", session).call();
-    match response {
-        Ok(response) => {
-            let text = response.into_string().context(AocError::Response)?;
-            Ok(text.trim_end().to_string())
+    fn wait_for_turn(&self) {
+        let path = &self.dir.join("last-request");
+        let last = fs::read_to_string(path)
+            .ok()
+            .and_then(|last| last.trim().parse::<u64>().ok())
+            .map(|last| UNIX_EPOCH + Duration::from_millis(last));
+        if let Some(elapsed) = last.and_then(|last| last.elapsed().ok()) {
+            if let Some(wait) = REQUEST_INTERVAL.checked_sub(elapsed) {
+                thread::sleep(wait);
+            }
         }
-        Err(e) => Err(match e {
-            ureq::Error::Status(_, response) => {
-                let text = response.into_string().context(AocError::Response)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let _ = fs::create_dir_all(&self.dir).and_then(|_| fs::write(path, now.to_string()));
+    }
+
+    fn cache_path(&self, url: &str) -> PathBuf {
+        let key = format!("{}\n{url}", self.session);
+        let hash = digest::digest(&digest::SHA256, key.as_bytes());
+        let name: String = hash.as_ref()[..16]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        self.dir.join("cache").join(name)
+    }
+
+    fn cached(&self, url: &str, ttl: Duration) -> Option<String> {
+        let path = &self.cache_path(url);
+        let age = fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()?;
+        (age < ttl).then(|| fs::read_to_string(path).ok())?
+    }
+
+    fn store(&self, url: &str, text: &str) {
+        let path = &self.cache_path(url);
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent).and_then(|_| fs::write(path, text));
+        }
+    }
+
+    fn stale(&self, url: &str) -> Option<String> {
+        fs::read_to_string(self.cache_path(url)).ok()
+    }
+
+    fn invalidate(&self, url: &str) {
+        let _ = fs::remove_file(self.cache_path(url));
+    }
+
+    pub fn get_user(&self) -> Result<String> {
+        let url = &format!("https://adventofcode.com/{}", date::latest_event_year());
+        let Page::Ok(text) = self.fetch(url)? else {
+            return AocError::PageAvailable.err();
+        };
+        let re = Regex::new(r#"<div class="user">([^<]+)"#).unwrap();
+        match re.captures(&text) {
+            Some(caps) => Ok(caps[1].trim().to_string()),
+            None => AocError::Session.err(),
+        }
+    }
+
+    pub fn get_events(&self) -> Result<Vec<Event>> {
+        let url = &format!(
+            "https://adventofcode.com/{}/events",
+            date::latest_event_year()
+        );
+        let Page::Ok(text) = self.get(url, Some(YEAR_TTL))? else {
+            return AocError::PageAvailable.err();
+        };
+        let re = Regex::new(
+            r#"<a href="/(\d{4})">\[\d{4}\]</a>(?:\s*<span class="star-count">(\d+)\*</span>)?"#,
+        )
+        .unwrap();
+        let mut events: Vec<_> = re
+            .captures_iter(&text)
+            .map(|caps| Event {
+                year: caps[1].to_string(),
+                stars: caps.get(2).and_then(|stars| stars.as_str().parse().ok()),
+            })
+            .collect();
+        if events.is_empty() {
+            return AocError::Response.err();
+        }
+        events.sort_by(|a, b| a.year.cmp(&b.year));
+        Ok(events)
+    }
+
+    pub fn get_leaderboard(&self, year: &str, id: &str) -> Result<Leaderboard> {
+        let url = &format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+        let Page::Ok(text) = self.get(url, Some(LEADERBOARD_TTL))? else {
+            return AocError::Leaderboard.err();
+        };
+        serde_json::from_str(&text).map_err(|e| {
+            self.invalidate(url);
+            e.error().context(AocError::Leaderboard)
+        })
+    }
+
+    pub fn get_stats(&self, year: &str) -> Result<Vec<DayStats>> {
+        let url = &format!("https://adventofcode.com/{year}/leaderboard/self");
+        let text = match self.get(url, Some(YEAR_TTL))? {
+            Page::Ok(text) => {
+                let re = Regex::new(r"\[Log In\]").unwrap();
+                if re.is_match(&text) {
+                    self.invalidate(url);
+                    return AocError::Session.err();
+                }
+                text
+            }
+            Page::Status(..) => return AocError::PageAvailable.err(),
+        };
+        let re = Regex::new(r"(?s)<pre[^>]*>(.*?)</pre>").unwrap();
+        let Some(table) = re.captures(&text) else {
+            return Ok(Vec::new());
+        };
+        let table = decode_entities(&table[1]);
+        let re = Regex::new(r"(?m)^\s*(\d+)((?:\s+\S+){3})((?:\s+\S+){3})?\s*$").unwrap();
+        let mut stats: Vec<_> = re
+            .captures_iter(&table)
+            .filter_map(|caps| {
+                Some(DayStats {
+                    day: caps[1].parse().ok().filter(|day| (1..=25).contains(day))?,
+                    parts: [caps.get(2), caps.get(3)]
+                        .map(|part| part.and_then(|part| PartStats::parse(part.as_str()))),
+                })
+            })
+            .collect();
+        stats.sort_by_key(|stats| stats.day);
+        Ok(stats)
+    }
+
+    pub fn get_input(&self, year: &str, day: &str) -> Result<String> {
+        let day = &day.parse::<u8>().unwrap().to_string();
+        let url = &format!("https://adventofcode.com/{year}/day/{day}/input");
+        match self.fetch(url)? {
+            Page::Ok(text) => Ok(text.trim_end().to_string()),
+            Page::Status(code, text) => {
                 let re = Regex::new(r"Puzzle inputs differ by user").unwrap();
                 if re.is_match(&text) {
-                    AocError::Session
+                    AocError::Session.err()
                 } else {
-                    AocError::PageAvailable
+                    Err(unavailable(code, year, day))
                 }
-                .into()
             }
-            ureq::Error::Transport(transport) => {
-                transport.to_string().error().context(AocError::Network)
+        }
+    }
+
+    pub fn get_progress(&self, year: &str, day: &str, cached: bool) -> Result<Progress> {
+        let day = &day.parse::<u8>().unwrap().to_string();
+        let url = &format!("https://adventofcode.com/{year}/day/{day}");
+        let text = match self.get(url, cached.then_some(DAY_TTL))? {
+            Page::Ok(text) => {
+                let re = Regex::new(r"To play, please identify yourself via one of these services")
+                    .unwrap();
+                if re.is_match(&text) {
+                    self.invalidate(url);
+                    return AocError::Session.err();
+                }
+                text
             }
-        }),
+            Page::Status(code, _) => return Err(unavailable(code, year, day)),
+        };
+        let re = Regex::new(r"Your puzzle answer was <code>([^<]+)</code>").unwrap();
+        let caps: Vec<_> = re.captures_iter(&text).take(2).collect();
+        let (part_1, part_2) = match caps.len() {
+            0 => (None, None),
+            1 => (Some(caps[0][1].to_string()), None),
+            2 => (Some(caps[0][1].to_string()), Some(caps[1][1].to_string())),
+            _ => unreachable!(),
+        };
+        let next =
+            (caps.len() <= (day != "25") as usize).then_some((caps.len() as u8 + 1).to_string());
+        Ok(Progress {
+            part_1,
+            part_2,
+            next,
+        })
     }
-}
 
-pub struct Progress {
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub next: Option<String>,
-}
+    pub fn submit(
+        &self,
+        year: &str,
+        day: &str,
+        part: &str,
+        answer: &str,
+    ) -> Result<SubmissionResult> {
+        let day = &day.parse::<u8>().unwrap().to_string();
+        let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
+        let params = &[("level", part), ("answer", answer)];
+        match self.post(&url, params)? {
+            Page::Ok(text) => {
+                let re = Regex::new(r"That's the right answer").unwrap();
+                if re.is_match(&text) {
+                    self.invalidate(&format!("https://adventofcode.com/{year}/day/{day}"));
+                    self.invalidate(&format!("https://adventofcode.com/{year}"));
+                    return Ok(SubmissionResult::Correct);
+                }
+                let re = Regex::new(r"That's not the right answer").unwrap();
+                if re.is_match(&text) {
+                    return Ok(SubmissionResult::Incorrect);
+                }
+                let re = Regex::new(r"You gave an answer too recently").unwrap();
+                if re.is_match(&text) {
+                    return Ok(SubmissionResult::Wait);
+                }
+                AocError::Response.err()
+            }
+            Page::Status(code, _) => Err(unavailable(code, year, day)),
+        }
+    }
 
-pub fn get_progress(year: &str, day: &str, session: &str) -> Result<Progress> {
-    let day = &day.parse::<u8>().unwrap().to_string();
-    let url = format!("https://adventofcode.com/{year}/day/{day}");
-    let response = ureq::get(&url).set("cookie", session).call();
-    let text = match response {
-        Ok(response) => {
-            let text = response.into_string().context(AocError::Response)?;
-            let re =
-                Regex::new(r"To play, please identify yourself via one of these services").unwrap();
-            if re.is_match(&text) {
-                return AocError::Session.err();
+    pub fn get_year_completion(&self, year: &str, cached: bool) -> Result<YearCompletion> {
+        let text = self.get_year_page(year, cached)?;
+        Ok(year_completion(&text))
+    }
+
+    pub fn get_calendar(&self, year: &str) -> Result<Calendar> {
+        let text = self.get_year_page(year, true)?;
+        let re = Regex::new(r#"(?s)<pre class="calendar[^"]*">(.*?)</pre>"#).unwrap();
+        let Some(caps) = re.captures(&text) else {
+            return AocError::Response.err();
+        };
+        let tag_re = Regex::new(r#"<(/?)(\w+)([^>]*)>|([^<]+)"#).unwrap();
+        let class_re = Regex::new(r#"class="([^"]*)""#).unwrap();
+        let mut calendar = Calendar::default();
+        let mut line = Vec::new();
+        let mut stack: Vec<(String, Vec<String>)> = Vec::new();
+        for token in tag_re.captures_iter(&caps[1]) {
+            if let Some(text) = token.get(4) {
+                if stack
+                    .iter()
+                    .any(|(tag, _)| tag == "script" || tag == "style")
+                {
+                    continue;
+                }
+                let kind = CalendarKind::of(&stack);
+                for (i, text) in decode_entities(text.as_str()).split('\n').enumerate() {
+                    if i > 0 {
+                        calendar.lines.push(std::mem::take(&mut line));
+                    }
+                    if !text.is_empty() {
+                        line.push(CalendarText {
+                            text: text.to_string(),
+                            kind: kind.clone(),
+                        });
+                    }
+                }
+                continue;
+            }
+            let tag = token[2].to_lowercase();
+            if &token[1] == "/" {
+                if let Some(i) = stack.iter().rposition(|(open, _)| *open == tag) {
+                    stack.truncate(i);
+                }
+                continue;
             }
-            text
+            if ["br", "img", "input", "meta", "link"].contains(&tag.as_str())
+                || token[3].ends_with('/')
+            {
+                continue;
+            }
+            let classes = class_re
+                .captures(&token[3])
+                .map(|caps| caps[1].split_whitespace().map(String::from).collect())
+                .unwrap_or_default();
+            stack.push((tag, classes));
+        }
+        if !line.is_empty() {
+            calendar.lines.push(line);
         }
-        Err(e) => {
-            return match e {
-                ureq::Error::Status(_, _) => AocError::PageAvailable.err(),
-                ureq::Error::Transport(transport) => {
-                    transport.to_string().err().context(AocError::Network)
+        while calendar.lines.last().is_some_and(|line| line.is_empty()) {
+            calendar.lines.pop();
+        }
+        calendar.stars = year_completion(&text).total;
+        Ok(calendar)
+    }
+
+    fn get_year_page(&self, year: &str, cached: bool) -> Result<String> {
+        let url = &format!("https://adventofcode.com/{year}");
+        match self.get(url, cached.then_some(YEAR_TTL))? {
+            Page::Ok(text) => {
+                let re = Regex::new(r"\[Log In\]").unwrap();
+                if re.is_match(&text) {
+                    self.invalidate(url);
+                    return AocError::Session.err();
                 }
+                Ok(text)
             }
+            Page::Status(..) => AocError::PageAvailable.err(),
+        }
+    }
+}
+
+fn page(result: Result<ureq::Response, ureq::Error>) -> Result<Page> {
+    match result {
+        Ok(response) => Ok(Page::Ok(
+            response.into_string().context(AocError::Response)?,
+        )),
+        Err(ureq::Error::Status(code, _)) if code >= 500 => AocError::Outage(code).err(),
+        Err(ureq::Error::Status(code, response)) => Ok(Page::Status(
+            code,
+            response.into_string().context(AocError::Response)?,
+        )),
+        Err(ureq::Error::Transport(transport)) => {
+            let error = match transport.kind() {
+                ureq::ErrorKind::Dns => AocError::Dns,
+                ureq::ErrorKind::InvalidProxyUrl
+                | ureq::ErrorKind::ProxyConnect
+                | ureq::ErrorKind::ProxyUnauthorized => AocError::Proxy,
+                _ => match io_error_kind(&transport) {
+                    Some(io::ErrorKind::TimedOut) => AocError::Timeout,
+                    Some(io::ErrorKind::InvalidData) => AocError::Tls,
+                    _ => AocError::Network,
+                },
+            };
+            transport.to_string().err().context(error)
+        }
+    }
+}
+
+fn is_transient(transport: &ureq::Transport) -> bool {
+    match transport.kind() {
+        ureq::ErrorKind::ConnectionFailed | ureq::ErrorKind::Io => {
+            io_error_kind(transport) != Some(io::ErrorKind::InvalidData)
         }
+        _ => false,
+    }
+}
+
+fn io_error_kind(transport: &ureq::Transport) -> Option<io::ErrorKind> {
+    std::error::Error::source(transport)?
+        .downcast_ref::<io::Error>()
+        .map(io::Error::kind)
+}
+
+fn proxy() -> Result<Option<ureq::Proxy>> {
+    let var = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
     };
-    let re = Regex::new(r"Your puzzle answer was <code>([^<]+)</code>").unwrap();
-    let caps: Vec<_> = re.captures_iter(&text).take(2).collect();
-    let (part_1, part_2) = match caps.len() {
-        0 => (None, None),
-        1 => (Some(caps[0][1].to_string()), None),
-        2 => (Some(caps[0][1].to_string()), Some(caps[1][1].to_string())),
-        _ => unreachable!(),
+    let bypass = var(&["NO_PROXY", "no_proxy"]).is_some_and(|hosts| {
+        hosts.split(',').any(|host| {
+            let host = host.trim().trim_start_matches('.');
+            host == "*" || HOST == host || HOST.ends_with(&format!(".{host}"))
+        })
+    });
+    if bypass {
+        return Ok(None);
+    }
+    let Some(url) = var(&["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]) else {
+        return Ok(None);
     };
-    let next = (caps.len() <= (day != "25") as usize).then_some((caps.len() as u8 + 1).to_string());
-    Ok(Progress {
-        part_1,
-        part_2,
-        next,
-    })
+    ureq::Proxy::new(&url)
+        .map(Some)
+        .context(AocError::Proxy)
+        .context(format!("invalid proxy URL `{url}`"))
 }
 
-pub enum SubmissionResult {
-    Correct,
-    Incorrect,
-    Wait,
+fn unavailable(code: u16, year: &str, day: &str) -> Error {
+    let wait = date::unlock_time(year.parse().unwrap(), day.parse().unwrap()) - date::now();
+    if code == 404 && wait > 0 {
+        AocError::Locked(display::clock(wait)).error()
+    } else {
+        AocError::PageAvailable.error()
+    }
 }
 
-pub fn submit(
-    year: &str,
-    day: &str,
-    part: &str,
-    answer: &str,
-    session: &str,
-) -> Result<SubmissionResult> {
-    let day = &day.parse::<u8>().unwrap().to_string();
-    let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
-    let params = &[("level", part), ("answer", answer)];
-    let response = ureq::post(&url).set("cookie", session).send_form(params);
-    match response {
-        Ok(response) => {
-            let text = response.into_string().context(AocError::Response)?;
-            let re = Regex::new(r"That's the right answer").unwrap();
-            if re.is_match(&text) {
-                return Ok(SubmissionResult::Correct);
-            }
-            let re = Regex::new(r"That's not the right answer").unwrap();
-            if re.is_match(&text) {
-                return Ok(SubmissionResult::Incorrect);
-            }
-            let re = Regex::new(r"You gave an answer too recently").unwrap();
-            if re.is_match(&text) {
-                return Ok(SubmissionResult::Wait);
-            }
-            AocError::Response.err()
+pub struct Event {
+    pub year: String,
+    pub stars: Option<u8>,
+}
+
+#[derive(Deserialize)]
+pub struct Leaderboard {
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u8,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
         }
-        Err(e) => match e {
-            ureq::Error::Status(_, _) => AocError::PageAvailable.err(),
-            ureq::Error::Transport(transport) => {
-                transport.to_string().err().context(AocError::Network)
-            }
-        },
+    }
+
+    pub fn star_time(&self, day: u8, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+            .map(|star| star.get_star_ts)
+    }
+
+    pub fn completion(&self) -> YearCompletion {
+        let mut completion = YearCompletion::default();
+        for day in 1..=25 {
+            let stars = (1..=2)
+                .filter(|&part| self.star_time(day, part).is_some())
+                .count();
+            completion.days[day as usize - 1] = match stars {
+                0 => DayCompletion::None,
+                1 => DayCompletion::Partial,
+                _ => DayCompletion::Full,
+            };
+        }
+        completion.total = self.stars;
+        completion
     }
 }
 
-pub struct YearCompletion {
-    pub days: [DayCompletion; 25],
-    pub total: u8,
+#[derive(Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
 }
 
-#[derive(Clone, Copy)]
-pub enum DayCompletion {
-    None,
-    Partial,
-    Full,
+pub struct DayStats {
+    pub day: u8,
+    pub parts: [Option<PartStats>; 2],
 }
 
-pub fn get_year_completion(year: &str, session: &str) -> Result<YearCompletion> {
-    let url = format!("https://adventofcode.com/{year}");
-    let response = ureq::get(&url).set("cookie", session).call();
-    let text = match response {
-        Ok(response) => {
-            let text = response.into_string().context(AocError::Response)?;
-            let re = Regex::new(r"\[Log In\]").unwrap();
-            if re.is_match(&text) {
-                return AocError::Session.err();
-            }
-            text
-        }
-        Err(e) => {
-            return match e {
-                ureq::Error::Status(_, _) => AocError::PageAvailable.err(),
-                ureq::Error::Transport(transport) => {
-                    transport.to_string().err().context(AocError::Network)
-                }
+pub struct PartStats {
+    pub time: Option<u32>,
+    pub rank: u32,
+    pub score: u32,
+}
+
+impl PartStats {
+    fn parse(columns: &str) -> Option<Self> {
+        let columns: Vec<_> = columns.split_whitespace().collect();
+        let [time, rank, score] = columns[..] else {
+            return None;
+        };
+        let time = match time {
+            ">24h" => None,
+            time => {
+                let units: Vec<u32> = time
+                    .split(':')
+                    .map(|unit| unit.parse().ok())
+                    .collect::<Option<_>>()?;
+                let [hours, minutes, seconds] = units[..] else {
+                    return None;
+                };
+                Some(hours * 3600 + minutes * 60 + seconds)
             }
-        }
-    };
+        };
+        Some(Self {
+            time,
+            rank: rank.parse().ok()?,
+            score: score.parse().ok()?,
+        })
+    }
+}
 
+pub struct Progress {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub next: Option<String>,
+}
+
+pub enum SubmissionResult {
+    Correct,
+    Incorrect,
+    Wait,
+}
+
+fn year_completion(text: &str) -> YearCompletion {
     let mut days = [DayCompletion::None; 25];
     let mut total = 0;
 
     for day in 1..=25 {
         let re = Regex::new(&format!("\"Day {day}, two stars\"")).unwrap();
-        if re.is_match(&text) {
+        if re.is_match(text) {
             days[day - 1] = DayCompletion::Full;
             total += 2;
             continue;
         }
         let re = Regex::new(&format!("\"Day {day}, one star\"")).unwrap();
-        if re.is_match(&text) {
+        if re.is_match(text) {
             days[day - 1] = DayCompletion::Partial;
             total += 1;
         }
     }
 
-    Ok(YearCompletion { days, total })
+    YearCompletion { days, total }
+}
+
+#[derive(Default)]
+pub struct Calendar {
+    pub lines: Vec<Vec<CalendarText>>,
+    pub stars: u8,
+}
+
+pub struct CalendarText {
+    pub text: String,
+    pub kind: CalendarKind,
+}
+
+#[derive(Clone)]
+pub enum CalendarKind {
+    Art(Option<String>),
+    Locked,
+    Day,
+    Star(DayCompletion),
+    Hidden,
+}
+
+impl CalendarKind {
+    fn of(stack: &[(String, Vec<String>)]) -> Self {
+        let classes = || stack.iter().rev().flat_map(|(_, classes)| classes);
+        let has = |class: &str| classes().any(|c| c == class);
+        let Some((_, day_classes)) = stack.iter().find(|(tag, _)| tag == "a") else {
+            return if classes().any(|class| is_day_class(class)) {
+                Self::Locked
+            } else {
+                Self::Art(art_class(stack))
+            };
+        };
+        let completion = if day_classes.iter().any(|c| c == "calendar-verycomplete") {
+            DayCompletion::Full
+        } else if day_classes.iter().any(|c| c == "calendar-complete") {
+            DayCompletion::Partial
+        } else {
+            DayCompletion::None
+        };
+        if has("calendar-mark-verycomplete") {
+            match completion {
+                DayCompletion::Full => Self::Star(completion),
+                _ => Self::Hidden,
+            }
+        } else if has("calendar-mark-complete") {
+            match completion {
+                DayCompletion::None => Self::Hidden,
+                _ => Self::Star(completion),
+            }
+        } else if has("calendar-day") {
+            Self::Day
+        } else {
+            Self::Art(art_class(stack))
+        }
+    }
+}
+
+fn is_day_class(class: &str) -> bool {
+    class
+        .strip_prefix("calendar-day")
+        .is_some_and(|day| !day.is_empty() && day.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn art_class(stack: &[(String, Vec<String>)]) -> Option<String> {
+    stack
+        .iter()
+        .rev()
+        .flat_map(|(_, classes)| classes.iter().rev())
+        .find(|class| {
+            !is_day_class(class)
+                && !["calendar", "calendar-complete", "calendar-verycomplete"]
+                    .contains(&class.as_str())
+        })
+        .cloned()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[derive(Default)]
+pub struct YearCompletion {
+    pub days: [DayCompletion; 25],
+    pub total: u8,
+}
+
+#[derive(Clone, Copy, Default)]
+pub enum DayCompletion {
+    #[default]
+    None,
+    Partial,
+    Full,
 }
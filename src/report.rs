@@ -0,0 +1,175 @@
+use crate::{
+    error::{AocError, Result, ToErr},
+    run::RunResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    Unknown,
+    Panic,
+    Unimplemented,
+    Timeout,
+    BuildError,
+}
+
+impl Outcome {
+    pub const ALL: [Self; 7] = [
+        Self::Correct,
+        Self::Incorrect,
+        Self::Unknown,
+        Self::Panic,
+        Self::Unimplemented,
+        Self::Timeout,
+        Self::BuildError,
+    ];
+
+    pub fn of(result: &RunResult, expected: Option<&str>) -> Self {
+        match result {
+            RunResult::Unimplemented => Self::Unimplemented,
+            RunResult::Panic { .. } => Self::Panic,
+            RunResult::Timeout => Self::Timeout,
+            RunResult::Success { answer, .. } => match expected {
+                Some(expected) if expected == answer => Self::Correct,
+                Some(_) => Self::Incorrect,
+                None => Self::Unknown,
+            },
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::Incorrect => "incorrect",
+            Self::Unknown => "unknown",
+            Self::Panic => "panic",
+            Self::Unimplemented => "unimplemented",
+            Self::Timeout => "timeout",
+            Self::BuildError => "build error",
+        }
+    }
+
+    fn exit_code(self) -> u8 {
+        match self {
+            Self::Correct | Self::Unknown | Self::Unimplemented => 0,
+            Self::Incorrect => 2,
+            Self::Panic => 4,
+            Self::Timeout => 8,
+            Self::BuildError => 16,
+        }
+    }
+}
+
+pub struct Case {
+    pub year: String,
+    pub day: String,
+    pub part: Option<String>,
+    pub input: Option<String>,
+    pub outcome: Outcome,
+    pub time: Option<u64>,
+    pub expected: Option<String>,
+    pub answer: Option<String>,
+    pub output: Option<String>,
+}
+
+impl Case {
+    pub fn new(
+        year: &str,
+        day: &str,
+        part: &str,
+        input: &str,
+        result: &RunResult,
+        expected: Option<&str>,
+    ) -> Self {
+        let (time, answer, output) = match result {
+            RunResult::Success { answer, time } => (Some(*time), Some(answer.clone()), None),
+            RunResult::Panic { output } => (None, None, Some(output.clone())),
+            RunResult::Timeout | RunResult::Unimplemented => (None, None, None),
+        };
+        Self {
+            year: year.into(),
+            day: day.into(),
+            part: Some(part.into()),
+            input: Some(input.into()),
+            outcome: Outcome::of(result, expected),
+            time,
+            expected: expected.map(String::from),
+            answer,
+            output,
+        }
+    }
+
+    pub fn build_error(year: &str, day: &str) -> Self {
+        Self {
+            year: year.into(),
+            day: day.into(),
+            part: None,
+            input: None,
+            outcome: Outcome::BuildError,
+            time: None,
+            expected: None,
+            answer: None,
+            output: None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Report {
+    counts: [u32; Outcome::ALL.len()],
+    cases: Vec<Case>,
+}
+
+impl Report {
+    pub fn record(&mut self, outcome: Outcome) {
+        self.counts[outcome as usize] += 1;
+    }
+
+    pub fn record_case(&mut self, case: Case) {
+        self.record(case.outcome);
+        self.cases.push(case);
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        for (count, other) in self.counts.iter_mut().zip(other.counts) {
+            *count += other;
+        }
+        self.cases.extend(other.cases);
+    }
+
+    pub fn cases(&self) -> &[Case] {
+        &self.cases
+    }
+
+    pub fn count(&self, outcome: Outcome) -> u32 {
+        self.counts[outcome as usize]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&count| count == 0)
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        Outcome::ALL
+            .into_iter()
+            .filter(|&outcome| self.count(outcome) > 0)
+            .fold(0, |code, outcome| code | outcome.exit_code())
+    }
+
+    pub fn result(&self) -> Result<()> {
+        let failures: u32 = Outcome::ALL
+            .into_iter()
+            .filter(|outcome| outcome.exit_code() != 0)
+            .map(|outcome| self.count(outcome))
+            .sum();
+        if failures == 0 {
+            Ok(())
+        } else {
+            AocError::Failures(failures)
+                .error()
+                .code(self.exit_code())
+                .err()
+        }
+    }
+}
@@ -1,74 +1,339 @@
-use std::{fs, path::Path, process::Command};
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{self, Command, ExitStatus, Stdio},
+    sync::atomic::{AtomicU32, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::{error::Result, file::PathInfo};
+use serde::Deserialize;
 
-pub fn build(path: &Path, debug: bool, show_output: bool) -> Result<BuildResult> {
+use crate::{
+    crypt,
+    display::TimeFormat,
+    error::{AocError, Result},
+    file::PathInfo,
+};
+
+pub struct BuildOptions {
+    pub profile: String,
+    pub features: Vec<String>,
+    pub config_features: Vec<String>,
+    pub rustflags: Option<String>,
+}
+
+pub struct RunOptions {
+    pub build: BuildOptions,
+    pub variant: Option<String>,
+    pub timeout: Option<Duration>,
+    pub actual: String,
+    pub timing: Timing,
+}
+
+#[derive(Default)]
+pub struct Timing {
+    pub format: TimeFormat,
+    pub days: HashMap<(String, String), (u64, u64)>,
+}
+
+impl Timing {
+    pub fn format(&self, year: &str, day: &str) -> TimeFormat {
+        match self.days.get(&(year.to_string(), day.to_string())) {
+            Some(&(fast, slow)) => TimeFormat {
+                fast,
+                slow,
+                ..self.format
+            },
+            None => self.format,
+        }
+    }
+}
+
+pub fn build(path: &Path, options: &BuildOptions, show_output: bool) -> Result<BuildResult> {
     let mut command = Command::new("cargo");
-    command.current_dir(path).arg("build");
-    if !debug {
-        command.arg("-r");
+    command
+        .current_dir(path)
+        .arg("build")
+        .arg("--message-format=json-render-diagnostics")
+        .arg("--profile")
+        .arg(&options.profile);
+    let declared = declared_features(path);
+    let features: Vec<_> = options
+        .features
+        .iter()
+        .chain(
+            options
+                .config_features
+                .iter()
+                .filter(|feature| declared.contains(feature)),
+        )
+        .map(String::as_str)
+        .collect();
+    if !features.is_empty() {
+        command.arg("--features").arg(features.join(","));
     }
-    let status = if show_output {
-        command.spawn()?.wait()?
+    if let Some(rustflags) = &options.rustflags {
+        let rustflags: Vec<_> = rustflags.split_whitespace().collect();
+        command
+            .arg("--config")
+            .arg(format!("build.rustflags={}", toml::Value::from(rustflags)));
+    }
+    let output = if show_output {
+        command.stdout(Stdio::piped()).spawn()?.wait_with_output()?
     } else {
-        command.output()?.status
+        command.output()?
     };
-    Ok(if status.success() {
-        BuildResult::Success
+    Ok(if output.status.success() {
+        BuildResult::Success(Executables::from_messages(&output.stdout, &options.profile))
     } else {
         BuildResult::Failure
     })
 }
 
+fn declared_features(path: &Path) -> Vec<String> {
+    path.join("Cargo.toml")
+        .read_file()
+        .ok()
+        .and_then(|info| info.get_contents())
+        .and_then(|contents| contents.parse::<toml::Table>().ok())
+        .and_then(|manifest| match manifest.get("features") {
+            Some(toml::Value::Table(features)) => Some(features.keys().cloned().collect()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
 pub enum BuildResult {
-    Success,
+    Success(Executables),
     Failure,
 }
 
-impl BuildResult {
-    pub fn success(&self) -> bool {
-        matches!(self, Self::Success)
+pub struct Executables {
+    bins: Vec<(String, PathBuf)>,
+    profile: String,
+}
+
+#[derive(Clone, Copy)]
+pub struct Executable<'a> {
+    path: &'a Path,
+    profile: &'a str,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    reason: String,
+    target: Option<Target>,
+    executable: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    name: String,
+    kind: Vec<String>,
+}
+
+impl Executables {
+    fn from_messages(stdout: &[u8], profile: &str) -> Self {
+        let mut bins = Vec::new();
+        for line in String::from_utf8_lossy(stdout).lines() {
+            let Ok(message) = serde_json::from_str::<Message>(line) else {
+                continue;
+            };
+            if message.reason != "compiler-artifact" {
+                continue;
+            }
+            if let (Some(target), Some(executable)) = (message.target, message.executable) {
+                if target.kind.iter().any(|kind| kind == "bin") {
+                    bins.push((target.name, executable));
+                }
+            }
+        }
+        Self {
+            bins,
+            profile: profile.into(),
+        }
+    }
+
+    pub fn day(&self, year: &str, day: &str, variant: Option<&str>) -> Result<Executable<'_>> {
+        let name = bin_name(year, day, variant);
+        self.bins
+            .iter()
+            .find(|(bin, _)| *bin == name)
+            .map(|(_, path)| Executable {
+                path,
+                profile: &self.profile,
+            })
+            .ok_or_else(|| AocError::MissingExecutable(name).into())
+    }
+
+    pub fn variants(&self, year: &str, day: &str) -> Vec<(&str, Executable<'_>)> {
+        let name = bin_name(year, day, None);
+        let mut variants: Vec<_> = self
+            .bins
+            .iter()
+            .filter_map(|(bin, path)| {
+                let variant = if *bin == name {
+                    "main"
+                } else {
+                    bin.strip_prefix(&name)?.strip_prefix('-')?
+                };
+                let executable = Executable {
+                    path,
+                    profile: &self.profile,
+                };
+                Some((variant, executable))
+            })
+            .collect();
+        variants.sort_by_key(|(variant, _)| (*variant != "main", *variant));
+        variants
+    }
+}
+
+pub fn bin_name(year: &str, day: &str, variant: Option<&str>) -> String {
+    match variant {
+        Some(variant) => format!("y{year}d{day}-{variant}"),
+        None => format!("y{year}d{day}"),
     }
 }
 
 pub fn run(
+    exe: Executable,
     path: &Path,
-    year: &str,
-    day: &str,
     input: &str,
     part: &str,
-    debug: bool,
     show_output: bool,
+    timeout: Option<Duration>,
 ) -> Result<RunResult> {
-    let exe = path
-        .parent()
-        .unwrap()
-        .parent()
-        .unwrap()
-        .join("target")
-        .join(if debug { "debug" } else { "release" })
-        .join(format!("y{year}d{day}"));
-    let mut command = Command::new(exe);
-    command.current_dir(path).arg(input).arg(part);
-    let status = if show_output {
-        command.spawn()?.wait()?
+    let input_path = &path.join("data").join(input).join("input");
+    let staged = if !input_path.try_is_file()? && crypt::sealed_path(input_path).try_is_file()? {
+        let contents = crypt::read(path, input_path)?.try_contents()?;
+        Some(Staged::new(input, &contents)?)
     } else {
-        command.output()?.status
+        None
+    };
+    let mut command = Command::new(exe.path);
+    command
+        .current_dir(staged.as_ref().map_or(path, |staged| &staged.0))
+        .arg(input)
+        .arg(part);
+    let Some((status, output)) = wait(command, show_output, timeout)? else {
+        return Ok(RunResult::Timeout);
     };
     if !status.success() {
-        return Ok(RunResult::Panic);
+        return Ok(RunResult::Panic { output });
     }
     let out = path.join("data").join(input).join(part).join("out");
+    if let Some(staged) = &staged {
+        staged.copy_out(input, part, &out)?;
+    }
     if out.join("unimplemented").try_is_file()? {
         return Ok(RunResult::Unimplemented);
     }
     let answer = fs::read_to_string(out.join("answer"))?;
     let time = fs::read_to_string(out.join("time"))?.parse::<u64>()?;
+    fs::write(out.join("profile"), exe.profile)?;
     Ok(RunResult::Success { answer, time })
 }
 
+struct Staged(PathBuf);
+
+impl Staged {
+    fn new(input: &str, contents: &str) -> Result<Self> {
+        static COUNT: AtomicU32 = AtomicU32::new(0);
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let staged = Self(env::temp_dir().join(format!("aocli-{}-{count}", process::id())));
+        create_private_dir(&staged.0)?;
+        let data_path = &staged.0.join("data").join(input);
+        fs::create_dir_all(data_path)?;
+        fs::write(data_path.join("input"), contents)?;
+        Ok(staged)
+    }
+
+    fn copy_out(&self, input: &str, part: &str, out: &Path) -> Result<()> {
+        let staged_out = &self.0.join("data").join(input).join(part).join("out");
+        fs::create_dir_all(out)?;
+        for file in ["unimplemented", "answer", "time"] {
+            if staged_out.join(file).try_is_file()? {
+                fs::copy(staged_out.join(file), out.join(file))?;
+            } else if out.join(file).try_is_file()? {
+                fs::remove_file(out.join(file))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Staged {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(unix)]
+fn create_private_dir(path: &Path) -> Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    Ok(fs::DirBuilder::new().mode(0o700).create(path)?)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(path: &Path) -> Result<()> {
+    Ok(fs::create_dir(path)?)
+}
+
+fn wait(
+    mut command: Command,
+    show_output: bool,
+    timeout: Option<Duration>,
+) -> Result<Option<(ExitStatus, String)>> {
+    if !show_output {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+    let mut child = command.spawn()?;
+    let stdout = child
+        .stdout
+        .take()
+        .map(|out| thread::spawn(|| read_all(out)));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|err| thread::spawn(|| read_all(err)));
+    let status = match timeout {
+        None => Some(child.wait()?),
+        Some(timeout) => {
+            let start = Instant::now();
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    break Some(status);
+                }
+                if start.elapsed() >= timeout {
+                    child.kill()?;
+                    child.wait()?;
+                    break None;
+                }
+                thread::sleep(Duration::from_millis(5));
+            }
+        }
+    };
+    let mut output = Vec::new();
+    for reader in [stdout, stderr].into_iter().flatten() {
+        output.extend(reader.join().unwrap_or_default());
+    }
+    let output = String::from_utf8_lossy(&output).into_owned();
+    Ok(status.map(|status| (status, output)))
+}
+
+fn read_all(mut reader: impl Read) -> Vec<u8> {
+    let mut output = Vec::new();
+    let _ = reader.read_to_end(&mut output);
+    output
+}
+
 pub enum RunResult {
     Unimplemented,
-    Panic,
+    Panic { output: String },
+    Timeout,
     Success { answer: String, time: u64 },
 }